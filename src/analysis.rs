@@ -0,0 +1,543 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::component::*;
+use super::error::Error;
+use super::network::{ComponentId, Graph, Network, NodeId};
+
+/// Energization state of a node or component
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EnergizationState {
+    /// Connected to a source
+    Energized,
+    /// Not connected to a source or earth
+    DeEnergized,
+    /// Connected to earth via a closed earthing switch, and not to a source
+    Earthed,
+}
+
+impl fmt::Display for EnergizationState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let statestr = match self {
+            EnergizationState::Energized => "Energized",
+            EnergizationState::DeEnergized => "De-energized",
+            EnergizationState::Earthed => "Earthed",
+        };
+        write!(f, "{}", statestr)
+    }
+}
+
+/// Result of an energization analysis; the [EnergizationState] of each node and component in a [Network]
+pub struct Energization {
+    nodes: HashMap<String, EnergizationState>,
+    components: HashMap<String, EnergizationState>,
+    earth_faults: Vec<String>,
+}
+
+impl Energization {
+    /// Analyse the given network.
+    ///
+    /// Energization starts at the network sources and at the nodes of generators and external grids, and propagates
+    /// * through circuit breakers and disconnectors that may conduct; closed, or in intermediate or bad position
+    /// * through all terminals of a transformer, line or cable
+    ///
+    /// Earthing starts at closed earthing switches and propagates through closed circuit breakers and disconnectors,
+    /// and through lines and cables.
+    /// A switch that is not in a definite position is thus assumed energized, but never earthed.
+    /// Nodes reached by both are reported as earth faults.
+    pub fn analyse(net: &Network) -> Energization {
+        net.graph().energization()
+    }
+
+    /// Analyse a locked graph, see [Energization::analyse]
+    fn of(g: &Graph) -> Energization {
+        let (energized, earthed) = reach(g, None);
+
+        let node_state = |id: &NodeId| {
+            if energized.contains(id) {
+                EnergizationState::Energized
+            } else if earthed.contains(id) {
+                EnergizationState::Earthed
+            } else {
+                EnergizationState::DeEnergized
+            }
+        };
+
+        let nodes = g
+            .node_ids()
+            .iter()
+            .filter_map(|id| Some((g.node(*id).ok()?.name().clone(), node_state(id))))
+            .collect();
+
+        let components = g
+            .components()
+            .map(|c| {
+                let states: Vec<EnergizationState> = c.nodes().iter().map(node_state).collect();
+                let state = if states.contains(&EnergizationState::Energized) {
+                    EnergizationState::Energized
+                } else if states.contains(&EnergizationState::Earthed) {
+                    EnergizationState::Earthed
+                } else {
+                    EnergizationState::DeEnergized
+                };
+                (c.name().clone(), state)
+            })
+            .collect();
+
+        let earth_faults = g
+            .node_ids()
+            .iter()
+            .filter(|id| energized.contains(id) && earthed.contains(id))
+            .filter_map(|id| Some(g.node(*id).ok()?.name().clone()))
+            .collect();
+
+        Energization {
+            nodes,
+            components,
+            earth_faults,
+        }
+    }
+
+    /// Get the [EnergizationState] of the node with the given name
+    pub fn node_state(&self, name: &str) -> Result<EnergizationState, Error> {
+        match self.nodes.get(name) {
+            Some(state) => Ok(*state),
+            None => Err(Error::NotFound {
+                kind: "node",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Get the [EnergizationState] of the component with the given name
+    pub fn component_state(&self, name: &str) -> Result<EnergizationState, Error> {
+        match self.components.get(name) {
+            Some(state) => Ok(*state),
+            None => Err(Error::NotFound {
+                kind: "component",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Return the names of the nodes that are both energized and earthed; a short circuit to earth
+    pub fn earth_faults(&self) -> &Vec<String> {
+        &self.earth_faults
+    }
+}
+
+impl Network {
+    /// Analyse the [EnergizationState] of each node and component in the network, see [Energization::analyse]
+    pub fn energization(&self) -> Energization {
+        Energization::analyse(self)
+    }
+
+    /// Check that closing the switchgear with the given name does not short an energized node to earth.
+    ///
+    /// The switch is assumed closed and energization and earthing are propagated as in [Energization::analyse];
+    /// closing is refused if that makes a node both energized and earthed that was not before. So
+    /// * A circuit breaker or disconnector may not join an energized part of the network with an earthed part
+    /// * An earthing switch may not close onto a node that is energized, directly or through a transformer, line or cable
+    pub fn check_earthing(&self, name: &str) -> Result<(), Error> {
+        let g = self.graph();
+        g.check_earthing(g.component_id(name)?)
+    }
+}
+
+impl Graph {
+    /// Analyse the [EnergizationState] of each node and component, see [Energization::analyse]
+    pub(crate) fn energization(&self) -> Energization {
+        Energization::of(self)
+    }
+
+    /// Check that closing the switchgear with the given id does not short an energized node to earth, see [Network::check_earthing]
+    pub(crate) fn check_earthing(&self, id: ComponentId) -> Result<(), Error> {
+        let c = self.component(id)?;
+        match c.r#type() {
+            ComponentType::CircuitBreaker
+            | ComponentType::Disconnector
+            | ComponentType::EarthingSwitch => {}
+            _ => return Ok(()),
+        }
+        let (energized, earthed) = reach(self, None);
+        let (closed_energized, closed_earthed) = reach(self, Some(id));
+        let mut faults: Vec<NodeId> = closed_energized
+            .intersection(&closed_earthed)
+            .filter(|n| !(energized.contains(n) && earthed.contains(n)))
+            .cloned()
+            .collect();
+        faults.sort();
+
+        // Report the node of the switch itself where possible; the earthed side for a circuit breaker or disconnector
+        let nodes = c.nodes();
+        let fault = nodes
+            .iter()
+            .find(|n| faults.contains(n) && earthed.contains(n))
+            .or_else(|| nodes.iter().find(|n| faults.contains(n)))
+            .or_else(|| faults.first());
+        match fault {
+            Some(node) => Err(Error::EarthFault {
+                component: c.name().clone(),
+                node: self.node(*node)?.name().clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// True if the component has a position and is closed
+fn is_closed(c: &dyn Component) -> bool {
+    match c.position() {
+        Ok(pos) => pos.read().unwrap().is_closed(),
+        Err(_) => false,
+    }
+}
+
+/// True if the component is a closed circuit breaker or disconnector
+pub(crate) fn is_closed_switch(c: &dyn Component) -> bool {
+    match c.r#type() {
+        ComponentType::CircuitBreaker | ComponentType::Disconnector => is_closed(c),
+        _ => false,
+    }
+}
+
+/// True if the component is a line or cable, which always connects its terminals
+fn is_conductor(c: &dyn Component) -> bool {
+    matches!(c.r#type(), ComponentType::Line | ComponentType::Cable)
+}
+
+/// True if the component is a generator or external grid, which energizes its node
+fn is_infeed(c: &dyn Component) -> bool {
+    matches!(
+        c.r#type(),
+        ComponentType::Generator | ComponentType::ExternalGrid
+    )
+}
+
+/// True if the component is a two- or three-winding transformer
+fn is_transformer(c: &dyn Component) -> bool {
+    matches!(
+        c.r#type(),
+        ComponentType::Transformer | ComponentType::ThreeWindingTransformer
+    )
+}
+
+/// True if the component is a circuit breaker or disconnector that is not definitely open
+fn may_conduct(c: &dyn Component) -> bool {
+    match (c.r#type(), c.position()) {
+        (ComponentType::CircuitBreaker | ComponentType::Disconnector, Ok(pos)) => {
+            pos.read().unwrap().may_conduct()
+        }
+        _ => false,
+    }
+}
+
+/// Get the ids of the energized and the earthed nodes, optionally assuming the switch with the given id is closed
+fn reach(g: &Graph, closed: Option<ComponentId>) -> (HashSet<NodeId>, HashSet<NodeId>) {
+    let closed = closed.and_then(|id| g.component(id).ok()).map(|c| c.name());
+    let closing = |c: &dyn Component| closed == Some(c.name());
+    let mut sources = g.sources().clone();
+    sources.extend(
+        g.components()
+            .filter(|c| is_infeed(c.as_ref()))
+            .flat_map(|c| c.nodes()),
+    );
+    let energized = propagate(g, &sources, |c| {
+        may_conduct(c) || is_conductor(c) || is_transformer(c) || closing(c)
+    });
+
+    let earths: Vec<NodeId> = g
+        .components()
+        .filter(|c| {
+            c.r#type() == ComponentType::EarthingSwitch
+                && (is_closed(c.as_ref()) || closing(c.as_ref()))
+        })
+        .flat_map(|c| c.nodes())
+        .collect();
+    let earthed = propagate(g, &earths, |c| {
+        is_closed_switch(c) || is_conductor(c) || closing(c)
+    });
+    (energized, earthed)
+}
+
+/// Get the ids of all nodes reachable from the start nodes via components that pass the filter
+fn propagate<F>(g: &Graph, start: &[NodeId], passes: F) -> HashSet<NodeId>
+where
+    F: Fn(&dyn Component) -> bool,
+{
+    let mut visited: HashSet<NodeId> = HashSet::new();
+    let mut queue: Vec<NodeId> = start.to_vec();
+    while let Some(node) = queue.pop() {
+        if !visited.insert(node) {
+            continue;
+        }
+        for id in g
+            .node_components(node)
+            .map(|v| v.as_slice())
+            .unwrap_or_default()
+        {
+            match g.component(*id) {
+                Ok(c) if passes(c.as_ref()) => queue.extend(c.nodes()),
+                _ => {}
+            }
+        }
+    }
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::SwitchPosition;
+
+    /// source - cb - node1 - ds - node2 - tf - node3, with an earthing switch on node2 and a voltage transformer on node3
+    fn create_test_network() -> Network {
+        let net = Network::new("net");
+        for n in ["source", "node1", "node2", "node3"] {
+            net.create_node(n).unwrap();
+        }
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.create_component::<Transformer>("tf").unwrap();
+        net.create_component::<EarthingSwitch>("es").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.connect("source", "cb", 0).unwrap();
+        net.connect("node1", "cb", 1).unwrap();
+        net.connect("node1", "ds", 0).unwrap();
+        net.connect("node2", "ds", 1).unwrap();
+        net.connect("node2", "tf", 0).unwrap();
+        net.connect("node3", "tf", 1).unwrap();
+        net.connect("node2", "es", 0).unwrap();
+        net.connect("node3", "vt", 0).unwrap();
+        net.add_source("source").unwrap();
+        net
+    }
+
+    #[test]
+    fn energization_no_sources() {
+        let net = create_test_network();
+        net.remove_source("source").unwrap();
+        let e = net.energization();
+
+        assert_eq!(
+            e.node_state("source").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        assert_eq!(
+            e.component_state("cb").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        assert!(e.node_state("does not exist").is_err());
+        assert!(e.component_state("does not exist").is_err());
+    }
+
+    #[test]
+    fn energization_propagation() {
+        let net = create_test_network();
+
+        let e = net.energization();
+        assert_eq!(
+            e.node_state("source").unwrap(),
+            EnergizationState::Energized
+        );
+        assert_eq!(
+            e.node_state("node1").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        assert_eq!(
+            e.component_state("cb").unwrap(),
+            EnergizationState::Energized
+        );
+        assert_eq!(
+            e.component_state("ds").unwrap(),
+            EnergizationState::DeEnergized
+        );
+
+        net.get_component("cb").unwrap().close().unwrap();
+        let e = net.energization();
+        assert_eq!(e.node_state("node1").unwrap(), EnergizationState::Energized);
+        assert_eq!(
+            e.node_state("node2").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        assert_eq!(
+            e.component_state("ds").unwrap(),
+            EnergizationState::Energized
+        );
+
+        net.get_component("ds").unwrap().close().unwrap();
+        let e = net.energization();
+        assert_eq!(e.node_state("node2").unwrap(), EnergizationState::Energized);
+        assert_eq!(e.node_state("node3").unwrap(), EnergizationState::Energized);
+        assert_eq!(
+            e.component_state("vt").unwrap(),
+            EnergizationState::Energized
+        );
+        assert_eq!(
+            e.component_state("es").unwrap(),
+            EnergizationState::Energized
+        );
+    }
+
+    #[test]
+    fn energization_earthed() {
+        let net = create_test_network();
+        net.get_component("es").unwrap().close().unwrap();
+        net.get_component("ds").unwrap().close().unwrap();
+
+        let e = net.energization();
+        assert_eq!(e.node_state("node1").unwrap(), EnergizationState::Earthed);
+        assert_eq!(e.node_state("node2").unwrap(), EnergizationState::Earthed);
+        assert_eq!(
+            e.node_state("node3").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        assert_eq!(e.component_state("es").unwrap(), EnergizationState::Earthed);
+        assert_eq!(e.component_state("tf").unwrap(), EnergizationState::Earthed);
+        assert_eq!(e.component_state("ds").unwrap(), EnergizationState::Earthed);
+        assert_eq!(
+            e.component_state("cb").unwrap(),
+            EnergizationState::Energized
+        );
+        assert!(e.earth_faults().is_empty());
+    }
+
+    #[test]
+    fn energization_earth_fault() {
+        let net = create_test_network();
+        net.close("es").unwrap();
+        net.close("ds").unwrap();
+        assert!(matches!(
+            net.close("cb"),
+            Err(Error::EarthFault { node, .. }) if node == "node1"
+        ));
+        assert!(net
+            .get_component("cb")
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_open());
+
+        net.open("es").unwrap();
+        net.close("cb").unwrap();
+        assert!(matches!(
+            net.close("es"),
+            Err(Error::EarthFault { node, .. }) if node == "node2"
+        ));
+
+        net.get_component("es").unwrap().close().unwrap();
+        let e = net.energization();
+        assert_eq!(
+            e.earth_faults(),
+            &vec![
+                "source".to_string(),
+                "node1".to_string(),
+                "node2".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn energization_earth_fault_transformer() {
+        let net = create_test_network();
+        net.create_component::<EarthingSwitch>("es3").unwrap();
+        net.connect("node3", "es3", 0).unwrap();
+        net.close("ds").unwrap();
+        net.close("es3").unwrap();
+        assert!(matches!(
+            net.close("cb"),
+            Err(Error::EarthFault { node, .. }) if node == "node3"
+        ));
+
+        net.open("es3").unwrap();
+        net.close("cb").unwrap();
+        assert!(matches!(
+            net.check_earthing("es3"),
+            Err(Error::EarthFault { node, .. }) if node == "node3"
+        ));
+        assert!(net.check_earthing("tf").is_ok());
+        assert!(net.check_earthing("does not exist").is_err());
+    }
+
+    #[test]
+    fn energization_infeed() {
+        let net = create_test_network();
+        net.remove_source("source").unwrap();
+        net.create_component::<ExternalGrid>("grid").unwrap();
+        net.connect("source", "grid", 0).unwrap();
+        net.create_component::<Generator>("gen").unwrap();
+        net.connect("node3", "gen", 0).unwrap();
+
+        let e = net.energization();
+        assert_eq!(
+            e.node_state("source").unwrap(),
+            EnergizationState::Energized
+        );
+        assert_eq!(e.node_state("node2").unwrap(), EnergizationState::Energized);
+        assert_eq!(
+            e.node_state("node1").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        assert!(matches!(
+            net.close("es"),
+            Err(Error::EarthFault { node, .. }) if node == "node2"
+        ));
+
+        net.disconnect("node3", "gen").unwrap();
+        let e = net.energization();
+        assert_eq!(
+            e.node_state("node2").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        net.close("es").unwrap();
+    }
+
+    #[test]
+    fn energization_uncertain_position() {
+        let net = create_test_network();
+        net.report_position("cb", SwitchPosition::Intermediate)
+            .unwrap();
+        let e = net.energization();
+        assert_eq!(e.node_state("node1").unwrap(), EnergizationState::Energized);
+        assert_eq!(
+            e.node_state("node2").unwrap(),
+            EnergizationState::DeEnergized
+        );
+
+        net.report_position("ds", SwitchPosition::Bad).unwrap();
+        net.get_component("es").unwrap().close().unwrap();
+        let e = net.energization();
+        assert_eq!(e.node_state("node2").unwrap(), EnergizationState::Energized);
+        assert_eq!(e.earth_faults(), &vec!["node2".to_string()]);
+    }
+
+    #[test]
+    fn energization_line() {
+        let net = create_test_network();
+        net.create_node("remote").unwrap();
+        net.create_component::<Line>("line").unwrap();
+        net.create_component::<EarthingSwitch>("remote_es").unwrap();
+        net.connect("node1", "line", 0).unwrap();
+        net.connect("remote", "line", 1).unwrap();
+        net.connect("remote", "remote_es", 0).unwrap();
+
+        net.close("cb").unwrap();
+        let e = net.energization();
+        assert_eq!(
+            e.node_state("remote").unwrap(),
+            EnergizationState::Energized
+        );
+
+        net.open("cb").unwrap();
+        net.close("remote_es").unwrap();
+        let e = net.energization();
+        assert_eq!(e.node_state("node1").unwrap(), EnergizationState::Earthed);
+        assert_eq!(
+            e.component_state("line").unwrap(),
+            EnergizationState::Earthed
+        );
+        assert!(matches!(net.close("cb"), Err(Error::EarthFault { .. })));
+    }
+}
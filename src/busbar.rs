@@ -0,0 +1,116 @@
+use std::fmt;
+
+use super::network::NodeId;
+
+/// The main bus of a voltage level; one or more nodes, its sections, with a rated voltage in kV.
+/// Sections are joined by bus couplers, which are circuit breakers, see [Network::add_coupler](super::network::Network::add_coupler).
+#[derive(Debug, PartialEq, Clone)]
+pub struct Busbar {
+    name: String,
+    rated_voltage: f64,
+    sections: Vec<NodeId>,
+}
+
+impl Busbar {
+    /// Constructor
+    pub fn new(name: &str, rated_voltage: f64, sections: Vec<NodeId>) -> Busbar {
+        Busbar {
+            name: name.to_string(),
+            rated_voltage,
+            sections,
+        }
+    }
+
+    /// Return the busbar name
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Return the rated voltage in kV
+    pub fn rated_voltage(&self) -> f64 {
+        self.rated_voltage
+    }
+
+    /// Return the id of the node of each section, in order
+    pub fn sections(&self) -> &Vec<NodeId> {
+        &self.sections
+    }
+
+    /// Return the busbar without the given section
+    pub(crate) fn without(&self, section: NodeId) -> Busbar {
+        Busbar {
+            name: self.name.clone(),
+            rated_voltage: self.rated_voltage,
+            sections: self
+                .sections
+                .iter()
+                .copied()
+                .filter(|s| *s != section)
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Busbar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Busbar {} ({} kV, {} sections)",
+            self.name,
+            self.rated_voltage,
+            self.sections.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::*;
+    use crate::network::{Network, RemoveMode};
+
+    #[test]
+    fn busbar_sections() {
+        let net = Network::new("net");
+        let sections = net.create_busbar("bb1", 110.0, 2).unwrap();
+        assert_eq!(
+            sections,
+            vec![net.node_id("bb1.1").unwrap(), net.node_id("bb1.2").unwrap()]
+        );
+        net.create_busbar("bb2", 110.0, 1).unwrap();
+        assert!(net.get_node("bb2").is_ok());
+        assert!(net.create_busbar("bb1", 110.0, 1).is_err());
+        assert!(net.create_busbar("bb3", -1.0, 1).is_err());
+        assert!(net.create_busbar("bb3", 110.0, 0).is_err());
+        assert_eq!(net.busbars().len(), 2);
+
+        let bb1 = net.busbar("bb1").unwrap();
+        assert_eq!(bb1.rated_voltage(), 110.0);
+        assert_eq!(bb1.to_string(), "Busbar bb1 (110 kV, 2 sections)");
+        assert_eq!(net.busbar_of(sections[1]).unwrap().name(), "bb1");
+
+        net.create_node("feeder").unwrap();
+        assert!(net.add_coupler("c1", "bb1.1", "feeder").is_err());
+        assert!(net.add_coupler("c1", "bb1.1", "bb1.1").is_err());
+        net.add_coupler("c1", "bb1.1", "bb1.2").unwrap();
+        net.add_coupler("c2", "bb1.2", "bb2").unwrap();
+        assert_eq!(
+            net.get_component("c1").unwrap().r#type(),
+            ComponentType::CircuitBreaker
+        );
+        assert_eq!(net.neighbours("bb1.2").unwrap(), vec!["c1", "c2"]);
+
+        // Removing a section node shrinks the busbar; removing the busbar removes its sections
+        net.remove_node("bb1.1", RemoveMode::Cascade).unwrap();
+        assert_eq!(net.busbar("bb1").unwrap().sections(), &vec![sections[1]]);
+        net.undo().unwrap();
+        assert_eq!(net.busbar("bb1").unwrap().sections(), &sections);
+        net.remove_busbar("bb1", RemoveMode::Cascade).unwrap();
+        assert!(net.busbar("bb1").is_err());
+        assert!(net.get_node("bb1.2").is_err());
+        assert!(net.busbar_of(sections[1]).is_none());
+
+        net.add_busbar("bb1", 20.0, &["feeder"]).unwrap();
+        assert!(net.add_busbar("bb4", 20.0, &["feeder"]).is_err());
+        assert!(net.add_busbar("bb4", 20.0, &["does not exist"]).is_err());
+    }
+}
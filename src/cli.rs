@@ -1,8 +1,16 @@
 use std::io::{stdin, stdout, Write};
 use std::process;
+use std::time::Duration;
 
 use super::component::*;
-use super::network::Network;
+use super::error::Error;
+use super::journal::EventSource;
+use super::json::{JsonReadable, JsonWritable};
+use super::network::{Network, RemoveMode};
+use super::parameters::LoadModel;
+use super::position::SwitchPosition;
+use super::sequence::Sequence;
+use super::tag::{Tag, TagKind};
 
 /// A CLI command
 #[derive(Debug, PartialEq)]
@@ -23,8 +31,58 @@ enum Command<'a> {
     Open(&'a str),
     /// Close switchgear command
     Close(&'a str),
+    /// Raise transformer tap position command
+    TapUp(&'a str),
+    /// Lower transformer tap position command
+    TapDown(&'a str),
     /// Update measurement value
     Update([&'a str; 2]),
+    /// Apply switchgear position reported by telemetry command
+    Report([&'a str; 2]),
+    /// Create busbar command
+    Busbar([&'a str; 3]),
+    /// Create bus coupler command
+    Coupler([&'a str; 3]),
+    /// Set electrical parameter of component command
+    Set([&'a str; 3]),
+    /// Set transformer vector group command
+    Vector([&'a str; 2]),
+    /// Set load model command
+    Model([&'a str; 2]),
+    /// Set switchgear operating time command
+    OperatingTime([&'a str; 2]),
+    /// Advance simulation clock command
+    Advance(&'a str),
+    /// Place safety tag on component command
+    TagAdd([&'a str; 3]),
+    /// Remove safety tag from component command
+    TagRemove([&'a str; 2]),
+    /// List safety tags, optionally of one component, command
+    TagList(Option<&'a str>),
+    /// Designate node as source command
+    Source(&'a str),
+    /// Show energization state of all nodes + components command
+    State(),
+    /// List interlocks of switchgear command
+    Interlocks(&'a str),
+    /// Show isolation and restoration programs of component command
+    Isolate(&'a str),
+    /// Dry run switching sequence file command
+    DryRun(&'a str),
+    /// Run switching sequence file command
+    Run(&'a str),
+    /// Save network to json file command
+    Save(&'a str),
+    /// Load network from json file command
+    Load(&'a str),
+    /// Undo last change command
+    Undo,
+    /// Redo last undone change command
+    Redo,
+    /// Show event journal, optionally of one component / node, command
+    Journal(Option<&'a str>),
+    /// Export event journal to csv or json file command
+    Export(&'a str),
     /// Exit program command
     Exit,
     /// Display help command
@@ -35,74 +93,127 @@ enum Command<'a> {
 
 /// Run the CLI loop; takes input from the user and executes the corresponding command
 pub fn run() {
-    let net = &Network::new("default_network");
+    let net = &mut Network::new("default_network");
+    net.set_event_source(EventSource::Cli);
     loop {
         let mut buf = String::new();
         print!(">");
         stdout().flush().unwrap();
         stdin().read_line(&mut buf).unwrap();
         let cmd = process_input(&buf);
-        match execute_command(net, cmd) {
-            Err(e) => {
-                println!("<{}", e);
-            }
-            Ok(_) => {}
+        if let Err(e) = execute_command(net, cmd) {
+            println!("<{}", e);
         }
     }
 }
 
-fn execute_command(net: &Network, cmd: Command) -> Result<(), String> {
+fn execute_command(net: &mut Network, cmd: Command) -> Result<(), Error> {
     match cmd {
-        Command::Create(args) => create(&net, args[0], args[1]),
-        Command::Delete(arg) => delete(&net, arg),
-        Command::List() => list(&net),
-        Command::Show(arg) => show(&net, arg),
-        Command::Connect(args) => connect(&net, args[0], args[1], args[2]),
-        Command::Disconnect(args) => disconnect(&net, args[0], args[1]),
-        Command::Open(arg) => open(&net, arg),
-        Command::Close(arg) => close(&net, arg),
-        Command::Update(args) => update(&net, args[0], args[1]),
+        Command::Create(args) => create(net, args[0], args[1]),
+        Command::Delete(arg) => delete(net, arg),
+        Command::List() => list(net),
+        Command::Show(arg) => show(net, arg),
+        Command::Connect(args) => connect(net, args[0], args[1], args[2]),
+        Command::Disconnect(args) => disconnect(net, args[0], args[1]),
+        Command::Open(arg) => open(net, arg),
+        Command::Close(arg) => close(net, arg),
+        Command::TapUp(arg) => tap(net, arg, true),
+        Command::TapDown(arg) => tap(net, arg, false),
+        Command::Update(args) => update(net, args[0], args[1]),
+        Command::Report(args) => report(net, args[0], args[1]),
+        Command::Busbar(args) => busbar(net, args[0], args[1], args[2]),
+        Command::Coupler(args) => coupler(net, args[0], args[1], args[2]),
+        Command::Set(args) => set(net, args[0], args[1], args[2]),
+        Command::Vector(args) => net.set_vector_group(args[0], args[1]),
+        Command::Model(args) => model(net, args[0], args[1]),
+        Command::OperatingTime(args) => operating_time(net, args[0], args[1]),
+        Command::Advance(arg) => advance(net, arg),
+        Command::TagAdd(args) => tag_add(net, args[0], args[1], args[2]),
+        Command::TagRemove(args) => tag_remove(net, args[0], args[1]),
+        Command::TagList(arg) => tag_list(net, arg),
+        Command::Source(arg) => source(net, arg),
+        Command::State() => state(net),
+        Command::Interlocks(arg) => interlocks(net, arg),
+        Command::Isolate(arg) => isolate(net, arg),
+        Command::DryRun(arg) => dry_run(net, arg),
+        Command::Run(arg) => run_sequence(net, arg),
+        Command::Save(arg) => save(net, arg),
+        Command::Load(arg) => load(net, arg),
+        Command::Undo => undo(net),
+        Command::Redo => redo(net),
+        Command::Journal(arg) => journal(net, arg),
+        Command::Export(arg) => export(net, arg),
         Command::Exit => process::exit(0),
         Command::Help => print_help(),
-        Command::Undefined => Err("Invalid command; type 'help' to see valid commands".to_string()),
+        Command::Undefined => Err(Error::InvalidArgument(
+            "Invalid command; type 'help' to see valid commands".to_string(),
+        )),
     }
 }
 
-fn create(net: &Network, _type: &str, name: &str) -> Result<(), String> {
+fn create(net: &Network, _type: &str, name: &str) -> Result<(), Error> {
     match _type {
         "cb" => {
             println!("<Created Circuit Breaker {}", name);
-            net.create_component::<CircuitBreaker>(&name)
+            net.create_component::<CircuitBreaker>(name).map(|_| ())
         }
         "ds" => {
             println!("<Created Disconnector {}", name);
-            net.create_component::<Disconnector>(&name)
+            net.create_component::<Disconnector>(name).map(|_| ())
         }
         "es" => {
             println!("<Created Disconnector {}", name);
-            net.create_component::<EarthingSwitch>(&name)
+            net.create_component::<EarthingSwitch>(name).map(|_| ())
         }
         "vt" => {
             println!("<Created Voltage Transformer {}", name);
-            net.create_component::<VoltageTransformer>(&name)
+            net.create_component::<VoltageTransformer>(name).map(|_| ())
         }
         "tf" => {
             println!("<Created Transformer {}", name);
-            net.create_component::<Transformer>(&name)
+            net.create_component::<Transformer>(name).map(|_| ())
+        }
+        "tf3" => {
+            println!("<Created Three-Winding Transformer {}", name);
+            net.create_component::<ThreeWindingTransformer>(name)
+                .map(|_| ())
+        }
+        "line" => {
+            println!("<Created Line {}", name);
+            net.create_component::<Line>(name).map(|_| ())
+        }
+        "cable" => {
+            println!("<Created Cable {}", name);
+            net.create_component::<Cable>(name).map(|_| ())
+        }
+        "load" => {
+            println!("<Created Load {}", name);
+            net.create_component::<Load>(name).map(|_| ())
+        }
+        "gen" => {
+            println!("<Created Generator {}", name);
+            net.create_component::<Generator>(name).map(|_| ())
+        }
+        "grid" => {
+            println!("<Created External Grid {}", name);
+            net.create_component::<ExternalGrid>(name).map(|_| ())
         }
         "node" => {
             println!("<Created Node {}", name);
-            net.create_node(&name)
+            net.create_node(name).map(|_| ())
         }
-        _ => Err(format!(
-            "{} type does not exist (cb, ds, es, vt, tf, node)",
+        _ => Err(Error::InvalidArgument(format!(
+            "{} type does not exist (cb, ds, es, vt, tf, tf3, line, cable, load, gen, grid, node)",
             _type
-        )),
+        ))),
     }
 }
 
-fn delete(net: &Network, name: &str) -> Result<(), String> {
-    match (net.remove_node(name), net.remove_component(name)) {
+fn delete(net: &Network, name: &str) -> Result<(), Error> {
+    match (
+        net.remove_node(name, RemoveMode::Cascade),
+        net.remove_component(name, RemoveMode::Cascade),
+    ) {
         (Ok(_), _) => {
             println!("<Deleted Node {}", name);
             Ok(())
@@ -111,27 +222,54 @@ fn delete(net: &Network, name: &str) -> Result<(), String> {
             println!("<Deleted Component {}", name);
             Ok(())
         }
-        (_, _) => Err(format!("No node or component with name {} exists", name)),
+        (_, _) => Err(Error::NotFound {
+            kind: "node or component",
+            name: name.to_string(),
+        }),
     }
 }
 
-fn list(net: &Network) -> Result<(), String> {
+fn list(net: &Network) -> Result<(), Error> {
     println!("<--Nodes--");
     net.list_nodes();
     println!("<--Components--");
     net.list_components();
+    println!("<--Busbars--");
+    for busbar in net.busbars() {
+        let mut sections = vec![];
+        for s in busbar.sections() {
+            sections.push(net.node(*s)?.name().clone());
+        }
+        println!("<{}: {}", busbar, sections.join(", "));
+    }
     Ok(())
 }
 
-fn show(net: &Network, name: &str) -> Result<(), String> {
+fn show(net: &Network, name: &str) -> Result<(), Error> {
     match (net.get_node(name), net.get_component(name)) {
         (Ok(n), _) => {
             println!("<{}", n);
         }
         (_, Ok(c)) => {
             println!("<{}", c);
+            for (parameter, value) in c.parameters() {
+                println!("<  {} = {}", parameter, value);
+            }
+            if let Ok(transformer) = c.transformer_parameters() {
+                let transformer = transformer.read().unwrap();
+                println!("<  vector group = {}", transformer.vector_group());
+                println!("<  {}", transformer.tap_changer());
+            }
+            if let Ok(load) = c.load_parameters() {
+                println!("<  model = {}", load.read().unwrap().model());
+            }
+        }
+        (_, _) => {
+            return Err(Error::NotFound {
+                kind: "node or component",
+                name: name.to_string(),
+            })
         }
-        (_, _) => return Err(format!("No node or component with name {} exists", name)),
     }
     Ok(())
 }
@@ -141,44 +279,304 @@ fn connect(
     node_name: &str,
     component_name: &str,
     terminal: &str,
-) -> Result<(), String> {
-    let terminal_usize: usize;
-    match terminal.parse::<usize>() {
-        Ok(t) => {
-            terminal_usize = t;
+) -> Result<(), Error> {
+    let terminal_usize = match terminal.parse::<usize>() {
+        Ok(t) => t,
+        Err(_) => {
+            return Err(Error::InvalidArgument(
+                "Terminal (3rd argument) is not an unsigned integer".to_string(),
+            ))
         }
-        Err(_) => return Err("Terminal (3rd argument) is not an unsigned integer".to_string()),
-    }
+    };
     net.connect(node_name, component_name, terminal_usize)
 }
 
-fn disconnect(net: &Network, node_name: &str, component_name: &str) -> Result<(), String> {
+fn disconnect(net: &Network, node_name: &str, component_name: &str) -> Result<(), Error> {
     net.disconnect(node_name, component_name)
 }
 
-fn open(net: &Network, component_name: &str) -> Result<(), String> {
-    let c = net.get_component(component_name)?;
-    c.open()
+fn open(net: &Network, component_name: &str) -> Result<(), Error> {
+    net.open(component_name)
+}
+
+fn close(net: &Network, component_name: &str) -> Result<(), Error> {
+    net.close(component_name)
+}
+
+fn tap(net: &Network, transformer_name: &str, raise: bool) -> Result<(), Error> {
+    if raise {
+        net.raise_tap(transformer_name)?;
+    } else {
+        net.lower_tap(transformer_name)?;
+    }
+    let tf = net.get_component(transformer_name)?;
+    let parameters = tf.transformer_parameters()?.read().unwrap();
+    println!("<{}", parameters.tap_changer());
+    if let Ok(ratio) = parameters.effective_ratio(0, 1) {
+        println!("<Effective ratio {:.4}", ratio);
+    }
+    Ok(())
+}
+
+fn update(net: &Network, component_name: &str, value: &str) -> Result<(), Error> {
+    if let Ok(v) = value.parse::<f64>() {
+        net.update(component_name, v)
+    } else {
+        Err(Error::InvalidArgument(
+            "Update value has to be a number".to_string(),
+        ))
+    }
+}
+
+fn report(net: &Network, component_name: &str, position: &str) -> Result<(), Error> {
+    let position = match position.to_lowercase().as_str() {
+        "open" => SwitchPosition::Open,
+        "closed" => SwitchPosition::Closed,
+        "intermediate" => SwitchPosition::Intermediate,
+        "bad" => SwitchPosition::Bad,
+        _ => {
+            return Err(Error::InvalidArgument(
+                "Reported position has to be open, closed, intermediate or bad".to_string(),
+            ))
+        }
+    };
+    net.report_position(component_name, position)
+}
+
+fn busbar(net: &Network, name: &str, rated_voltage: &str, sections: &str) -> Result<(), Error> {
+    match (rated_voltage.parse::<f64>(), sections.parse::<usize>()) {
+        (Ok(kv), Ok(n)) => {
+            net.create_busbar(name, kv, n)?;
+            println!("<Created {}", net.busbar(name)?);
+            Ok(())
+        }
+        _ => Err(Error::InvalidArgument(
+            "Rated voltage has to be a number and sections a positive integer".to_string(),
+        )),
+    }
 }
 
-fn close(net: &Network, component_name: &str) -> Result<(), String> {
-    let c = net.get_component(component_name)?;
-    c.close()
+fn coupler(net: &Network, name: &str, section_a: &str, section_b: &str) -> Result<(), Error> {
+    net.add_coupler(name, section_a, section_b)?;
+    println!("<Created Bus Coupler {}", name);
+    Ok(())
 }
 
-fn update(net: &Network, component_name: &str, value: &str) -> Result<(), String> {
-    let c = net.get_component(component_name)?;
+fn set(net: &Network, component_name: &str, parameter: &str, value: &str) -> Result<(), Error> {
     if let Ok(v) = value.parse::<f64>() {
-        c.update(v)
+        net.set_parameter(component_name, parameter, v)
     } else {
-        Err("Update value has to be a number".to_string())
+        Err(Error::InvalidArgument(
+            "Parameter value has to be a number".to_string(),
+        ))
+    }
+}
+
+fn model(net: &Network, load_name: &str, model: &str) -> Result<(), Error> {
+    let model = match model.to_lowercase().as_str() {
+        "constant-power" => LoadModel::ConstantPower,
+        "constant-impedance" => LoadModel::ConstantImpedance,
+        _ => {
+            return Err(Error::InvalidArgument(
+                "Load model has to be constant-power or constant-impedance".to_string(),
+            ))
+        }
+    };
+    net.set_load_model(load_name, model)
+}
+
+fn seconds(value: &str) -> Result<Duration, Error> {
+    match value.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(s)) => Ok(s),
+        _ => Err(Error::InvalidArgument(
+            "Time has to be a non-negative number of seconds".to_string(),
+        )),
+    }
+}
+
+fn operating_time(net: &Network, component_name: &str, value: &str) -> Result<(), Error> {
+    net.set_operating_time(component_name, seconds(value)?)
+}
+
+fn advance(net: &Network, value: &str) -> Result<(), Error> {
+    net.advance(seconds(value)?)?;
+    println!("<Simulation time {:.3} s", net.time().as_secs_f64());
+    for op in net.operations() {
+        println!(
+            "<  {} {} completes at {:.3} s",
+            op.operation(),
+            net.component(op.component())?.name(),
+            op.completes().as_secs_f64()
+        );
+    }
+    Ok(())
+}
+
+fn tag_add(net: &Network, component_name: &str, kind: &str, tag_name: &str) -> Result<(), Error> {
+    let kind = match kind.to_lowercase().as_str() {
+        "do-not-operate" => TagKind::DoNotOperate,
+        "locked-open" => TagKind::LockedOpen,
+        "out-of-service" => TagKind::OutOfService,
+        "under-maintenance" => TagKind::UnderMaintenance,
+        _ => return Err(Error::InvalidArgument(
+            "Tag kind has to be do-not-operate, locked-open, out-of-service or under-maintenance"
+                .to_string(),
+        )),
+    };
+    let tag = Tag::new(tag_name, kind);
+    net.add_tag(component_name, tag.clone())?;
+    println!("<Tagged {} {}", component_name, tag);
+    Ok(())
+}
+
+fn tag_remove(net: &Network, component_name: &str, tag_name: &str) -> Result<(), Error> {
+    net.remove_tag(component_name, tag_name)?;
+    println!("<Removed tag {} from {}", tag_name, component_name);
+    Ok(())
+}
+
+fn tag_list(net: &Network, component_name: Option<&str>) -> Result<(), Error> {
+    let names = match component_name {
+        Some(name) => vec![net.get_component(name)?.name().clone()],
+        None => net.components().iter().map(|c| c.name().clone()).collect(),
+    };
+    let mut count = 0;
+    for name in names {
+        for tag in net.tags(&name)? {
+            println!("<{} - {}", name, tag);
+            count += 1;
+        }
+    }
+    if count == 0 {
+        println!("<No tags placed");
+    }
+    Ok(())
+}
+
+fn source(net: &Network, node_name: &str) -> Result<(), Error> {
+    net.add_source(node_name)?;
+    println!("<Designated Node {} as source", node_name);
+    Ok(())
+}
+
+fn state(net: &Network) -> Result<(), Error> {
+    let e = net.energization();
+    println!("<--Nodes--");
+    for node in net.nodes() {
+        println!("<{} - {}", node, e.node_state(node.name())?);
+    }
+    println!("<--Components--");
+    for component in net.components() {
+        println!("<{} - {}", component, e.component_state(component.name())?);
     }
+    for node in e.earth_faults() {
+        println!("<Earth fault at Node {}", node);
+    }
+    Ok(())
 }
 
-fn process_input(buf: &str) -> Command {
+fn interlocks(net: &Network, component_name: &str) -> Result<(), Error> {
+    net.get_component(component_name)?.position()?;
+    let rules = net.interlocks(component_name);
+    if rules.is_empty() {
+        println!("<No interlocks apply to {}", component_name);
+    }
+    for rule in rules {
+        println!("<{}", rule);
+    }
+    Ok(())
+}
+
+fn isolate(net: &Network, component_name: &str) -> Result<(), Error> {
+    let p = net.isolation_procedure(component_name)?;
+    println!("<--Isolation of {}--", p.target());
+    for (i, step) in p.isolation_steps().iter().enumerate() {
+        println!("<{}. {}", i + 1, step);
+    }
+    println!("<--Restoration of {}--", p.target());
+    for (i, step) in p.restoration_steps().iter().enumerate() {
+        println!("<{}. {}", i + 1, step);
+    }
+    Ok(())
+}
+
+fn dry_run(net: &Network, path: &str) -> Result<(), Error> {
+    let d = Sequence::from_file(path)?.dry_run(net)?;
+    for (i, (step, e)) in d.steps().iter().enumerate() {
+        println!("<{}. {}", i + 1, step);
+        for node in net.nodes() {
+            println!("<  {} - {}", node, e.node_state(node.name())?);
+        }
+    }
+    match d.violation() {
+        Some((step, e)) => {
+            println!("<{}. {} -- {}", d.steps().len() + 1, step, e);
+            Ok(())
+        }
+        None => {
+            println!("<Dry run of {} completed without violations", path);
+            Ok(())
+        }
+    }
+}
+
+fn run_sequence(net: &Network, path: &str) -> Result<(), Error> {
+    let s = Sequence::from_file(path)?;
+    s.run(net)?;
+    println!("<Ran {} steps from {}", s.steps().len(), path);
+    Ok(())
+}
+
+fn save(net: &Network, path: &str) -> Result<(), Error> {
+    net.to_json(path)?;
+    println!("<Saved {} to {}", net, path);
+    Ok(())
+}
+
+fn load(net: &mut Network, path: &str) -> Result<(), Error> {
+    *net = *Network::from_json(path)?;
+    net.set_event_source(EventSource::Cli);
+    println!("<Loaded {} from {}", net, path);
+    Ok(())
+}
+
+fn undo(net: &Network) -> Result<(), Error> {
+    net.undo()?;
+    println!("<Undid last change");
+    Ok(())
+}
+
+fn redo(net: &Network) -> Result<(), Error> {
+    net.redo()?;
+    println!("<Redid last undone change");
+    Ok(())
+}
+
+fn journal(net: &Network, name: Option<&str>) -> Result<(), Error> {
+    let j = match name {
+        Some(name) => net.journal().for_name(name),
+        None => net.journal(),
+    };
+    for event in j.events() {
+        println!("<{}", event);
+    }
+    Ok(())
+}
+
+fn export(net: &Network, path: &str) -> Result<(), Error> {
+    if path.ends_with(".csv") {
+        net.journal().to_csv(path)?;
+    } else {
+        net.journal().to_json(path)?;
+    }
+    println!("<Exported journal to {}", path);
+    Ok(())
+}
+
+fn process_input(buf: &str) -> Command<'_> {
     let buf = buf.trim();
     let split: Vec<&str> = buf.split(' ').collect();
-    if split.len() == 0 {
+    if split.is_empty() {
         return Command::Undefined;
     }
     let cmd: &str = &split[0].to_lowercase();
@@ -198,7 +596,7 @@ fn process_input(buf: &str) -> Command {
             }
         }
         "list" => {
-            if split.len() < 1 {
+            if split.is_empty() {
                 Command::Undefined
             } else {
                 Command::List()
@@ -239,6 +637,20 @@ fn process_input(buf: &str) -> Command {
                 Command::Close(split[1])
             }
         }
+        "tapup" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::TapUp(split[1])
+            }
+        }
+        "tapdown" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::TapDown(split[1])
+            }
+        }
         "update" => {
             if split.len() < 3 {
                 Command::Undefined
@@ -246,13 +658,138 @@ fn process_input(buf: &str) -> Command {
                 Command::Update([split[1], split[2]])
             }
         }
+        "report" => {
+            if split.len() < 3 {
+                Command::Undefined
+            } else {
+                Command::Report([split[1], split[2]])
+            }
+        }
+        "busbar" => {
+            if split.len() < 4 {
+                Command::Undefined
+            } else {
+                Command::Busbar([split[1], split[2], split[3]])
+            }
+        }
+        "coupler" => {
+            if split.len() < 4 {
+                Command::Undefined
+            } else {
+                Command::Coupler([split[1], split[2], split[3]])
+            }
+        }
+        "set" => {
+            if split.len() < 4 {
+                Command::Undefined
+            } else {
+                Command::Set([split[1], split[2], split[3]])
+            }
+        }
+        "vector" => {
+            if split.len() < 3 {
+                Command::Undefined
+            } else {
+                Command::Vector([split[1], split[2]])
+            }
+        }
+        "model" => {
+            if split.len() < 3 {
+                Command::Undefined
+            } else {
+                Command::Model([split[1], split[2]])
+            }
+        }
+        "optime" => {
+            if split.len() < 3 {
+                Command::Undefined
+            } else {
+                Command::OperatingTime([split[1], split[2]])
+            }
+        }
+        "tag" => match (
+            split.get(1).map(|s| s.to_lowercase()).as_deref(),
+            split.len(),
+        ) {
+            (Some("add"), n) if n >= 5 => Command::TagAdd([split[2], split[3], split[4]]),
+            (Some("remove"), n) if n >= 4 => Command::TagRemove([split[2], split[3]]),
+            (Some("list"), _) => Command::TagList(split.get(2).copied()),
+            _ => Command::Undefined,
+        },
+        "advance" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Advance(split[1])
+            }
+        }
+        "source" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Source(split[1])
+            }
+        }
+        "state" => Command::State(),
+        "interlocks" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Interlocks(split[1])
+            }
+        }
+        "isolate" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Isolate(split[1])
+            }
+        }
+        "dryrun" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::DryRun(split[1])
+            }
+        }
+        "run" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Run(split[1])
+            }
+        }
+        "save" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Save(split[1])
+            }
+        }
+        "load" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Load(split[1])
+            }
+        }
+        "undo" => Command::Undo,
+        "redo" => Command::Redo,
+        "journal" => Command::Journal(split.get(1).copied()),
+        "export" => {
+            if split.len() < 2 {
+                Command::Undefined
+            } else {
+                Command::Export(split[1])
+            }
+        }
         "exit" => Command::Exit,
         "help" => Command::Help,
         _ => Command::Undefined,
     }
 }
 
-fn print_help() -> Result<(), String> {
+fn print_help() -> Result<(), Error> {
     println!("<create <{{node/component type}}> <name> -- Create a node/component");
     println!("<delete <name> -- Delete a node/component");
     println!("<list -- List all nodes/components");
@@ -261,7 +798,36 @@ fn print_help() -> Result<(), String> {
     println!("<disconnect <node_name> <component_name> -- Disconnect a connected component terminal and node");
     println!("<open <switchgear_name> -- Open a closed switchgear");
     println!("<close <switchgear_name> -- Close an open switchgear");
+    println!(
+        "<tapup <transformer_name> -- Raise the tap position of a transformer on-load tap changer, by default -8..8 in steps of 1.25 %"
+    );
+    println!("<tapdown <transformer_name> -- Lower the tap position of a transformer on-load tap changer");
     println!("<update <measurement_name> -- Update a measuremment value");
+    println!("<report <switchgear_name> <open/closed/intermediate/bad> -- Apply a switchgear position reported by telemetry");
+    println!("<busbar <name> <rated_kv> <sections> -- Create a busbar; its section nodes are named <name> or <name>.1, <name>.2, ...");
+    println!("<coupler <name> <section_name> <section_name> -- Create a bus coupler circuit breaker between two busbar sections");
+    println!("<set <component_name> <parameter> <value> -- Set an electrical parameter of a component, e.g. r, x, b, length or rating of a line/cable, sn, u1, u2, uk, xr or tap_min, tap_max, tap_step of a transformer");
+    println!("<vector <transformer_name> <vector_group> -- Set the vector group of a transformer, e.g. Dyn11 or YNyn0d5");
+    println!("<model <load_name> <constant-power/constant-impedance> -- Set the model of a load");
+    println!("<optime <switchgear_name> <seconds> -- Set the operating time of a circuit breaker/disconnector");
+    println!("<tag add <component_name> <do-not-operate/locked-open/out-of-service/under-maintenance> <tag_name> -- Place a safety tag; tagged switchgear can not be operated");
+    println!("<tag remove <component_name> <tag_name> -- Remove a safety tag");
+    println!(
+        "<tag list [component_name] -- List the safety tags of all components, or of one component"
+    );
+    println!("<advance <seconds> -- Advance the simulation clock, completing switchgear operations that are due");
+    println!("<source <node_name> -- Designate a node as an energization source");
+    println!("<state -- Show the energization state of all nodes/components");
+    println!("<interlocks <switchgear_name> -- List the interlocks that apply to a switchgear");
+    println!("<isolate <component_name> -- Show the switching programs to isolate and restore a component");
+    println!("<dryrun <path> -- Validate a switching sequence file, showing the energization state after each step");
+    println!("<run <path> -- Validate and apply a switching sequence file");
+    println!("<save <path> -- Save the network to a json file");
+    println!("<load <path> -- Load a network from a json file");
+    println!("<undo -- Undo the last change to the network");
+    println!("<redo -- Redo the last undone change");
+    println!("<journal [name] -- Show the event journal, optionally only of one node/component");
+    println!("<export <path> -- Export the event journal to a csv file (.csv) or json file");
     println!("<exit -- Exit the program");
     println!("<help -- Show this help text");
     Ok(())
@@ -270,6 +836,7 @@ fn print_help() -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::interlock::Interlock;
 
     #[test]
     fn cli_process_input() {
@@ -355,6 +922,11 @@ mod tests {
         );
         assert_eq!(process_input("close"), Command::Undefined);
 
+        assert_eq!(process_input("tapup Arg1"), Command::TapUp("Arg1"));
+        assert_eq!(process_input("TapDown Arg1"), Command::TapDown("Arg1"));
+        assert_eq!(process_input("tapup"), Command::Undefined);
+        assert_eq!(process_input("tapdown"), Command::Undefined);
+
         assert_eq!(
             process_input("update Arg1 Arg2"),
             Command::Update(["Arg1", "Arg2"])
@@ -370,6 +942,115 @@ mod tests {
         assert_eq!(process_input("update"), Command::Undefined);
         assert_eq!(process_input("update Arg1"), Command::Undefined);
 
+        assert_eq!(
+            process_input("report Arg1 Arg2"),
+            Command::Report(["Arg1", "Arg2"])
+        );
+        assert_eq!(
+            process_input("Report Arg1 Arg2 junk"),
+            Command::Report(["Arg1", "Arg2"])
+        );
+        assert_eq!(process_input("report Arg1"), Command::Undefined);
+
+        assert_eq!(
+            process_input("busbar Arg1 Arg2 Arg3"),
+            Command::Busbar(["Arg1", "Arg2", "Arg3"])
+        );
+        assert_eq!(process_input("busbar Arg1 Arg2"), Command::Undefined);
+        assert_eq!(
+            process_input("Coupler Arg1 Arg2 Arg3"),
+            Command::Coupler(["Arg1", "Arg2", "Arg3"])
+        );
+        assert_eq!(process_input("coupler Arg1 Arg2"), Command::Undefined);
+
+        assert_eq!(
+            process_input("set Arg1 Arg2 Arg3"),
+            Command::Set(["Arg1", "Arg2", "Arg3"])
+        );
+        assert_eq!(process_input("set Arg1 Arg2"), Command::Undefined);
+
+        assert_eq!(
+            process_input("vector Arg1 Arg2"),
+            Command::Vector(["Arg1", "Arg2"])
+        );
+        assert_eq!(process_input("vector Arg1"), Command::Undefined);
+
+        assert_eq!(
+            process_input("model Arg1 Arg2"),
+            Command::Model(["Arg1", "Arg2"])
+        );
+        assert_eq!(process_input("model Arg1"), Command::Undefined);
+
+        assert_eq!(
+            process_input("optime Arg1 Arg2"),
+            Command::OperatingTime(["Arg1", "Arg2"])
+        );
+        assert_eq!(process_input("optime Arg1"), Command::Undefined);
+
+        assert_eq!(process_input("advance Arg1"), Command::Advance("Arg1"));
+        assert_eq!(process_input("Advance Arg1"), Command::Advance("Arg1"));
+        assert_eq!(process_input("advance"), Command::Undefined);
+
+        assert_eq!(
+            process_input("tag add Arg1 Arg2 Arg3"),
+            Command::TagAdd(["Arg1", "Arg2", "Arg3"])
+        );
+        assert_eq!(
+            process_input("Tag Remove Arg1 Arg2"),
+            Command::TagRemove(["Arg1", "Arg2"])
+        );
+        assert_eq!(process_input("tag list"), Command::TagList(None));
+        assert_eq!(
+            process_input("tag list Arg1"),
+            Command::TagList(Some("Arg1"))
+        );
+        assert_eq!(process_input("tag add Arg1 Arg2"), Command::Undefined);
+        assert_eq!(process_input("tag remove Arg1"), Command::Undefined);
+        assert_eq!(process_input("tag"), Command::Undefined);
+
+        assert_eq!(process_input("source Arg1"), Command::Source("Arg1"));
+        assert_eq!(process_input("Source Arg1"), Command::Source("Arg1"));
+        assert_eq!(process_input("source"), Command::Undefined);
+
+        assert_eq!(process_input("state"), Command::State());
+        assert_eq!(process_input("State junk data here"), Command::State());
+
+        assert_eq!(
+            process_input("interlocks Arg1"),
+            Command::Interlocks("Arg1")
+        );
+        assert_eq!(process_input("interlocks"), Command::Undefined);
+
+        assert_eq!(process_input("isolate Arg1"), Command::Isolate("Arg1"));
+        assert_eq!(process_input("isolate"), Command::Undefined);
+
+        assert_eq!(process_input("dryrun Arg1"), Command::DryRun("Arg1"));
+        assert_eq!(process_input("dryrun"), Command::Undefined);
+        assert_eq!(process_input("run Arg1"), Command::Run("Arg1"));
+        assert_eq!(process_input("run"), Command::Undefined);
+
+        assert_eq!(process_input("save Arg1"), Command::Save("Arg1"));
+        assert_eq!(
+            process_input("Save Arg1 junk data here"),
+            Command::Save("Arg1")
+        );
+        assert_eq!(process_input("save"), Command::Undefined);
+
+        assert_eq!(process_input("load Arg1"), Command::Load("Arg1"));
+        assert_eq!(process_input("Load Arg1"), Command::Load("Arg1"));
+        assert_eq!(process_input("load"), Command::Undefined);
+
+        assert_eq!(process_input("undo"), Command::Undo);
+        assert_eq!(process_input("Redo"), Command::Redo);
+
+        assert_eq!(process_input("journal"), Command::Journal(None));
+        assert_eq!(
+            process_input("journal Arg1"),
+            Command::Journal(Some("Arg1"))
+        );
+        assert_eq!(process_input("export Arg1"), Command::Export("Arg1"));
+        assert_eq!(process_input("export"), Command::Undefined);
+
         assert_eq!(process_input("exit"), Command::Exit);
         assert_eq!(process_input("Exit"), Command::Exit);
         assert_eq!(process_input("exit and some more stuff"), Command::Exit);
@@ -388,7 +1069,7 @@ mod tests {
 
     #[test]
     fn cli_commands() {
-        let net = &Network::new("net");
+        let net = &mut Network::new("net");
 
         // Create
         assert!(execute_command(net, Command::Create(["cb", "1"])).is_ok());
@@ -420,6 +1101,37 @@ mod tests {
             net.get_component("5").unwrap().r#type(),
             ComponentType::Transformer
         );
+        assert!(execute_command(net, Command::Create(["tf3", "tf3"])).is_ok());
+        assert_eq!(
+            net.get_component("tf3").unwrap().r#type(),
+            ComponentType::ThreeWindingTransformer
+        );
+
+        assert!(execute_command(net, Command::Create(["line", "line"])).is_ok());
+        assert_eq!(
+            net.get_component("line").unwrap().r#type(),
+            ComponentType::Line
+        );
+        assert!(execute_command(net, Command::Create(["cable", "cable"])).is_ok());
+        assert_eq!(
+            net.get_component("cable").unwrap().r#type(),
+            ComponentType::Cable
+        );
+        assert!(execute_command(net, Command::Create(["load", "load"])).is_ok());
+        assert_eq!(
+            net.get_component("load").unwrap().r#type(),
+            ComponentType::Load
+        );
+        assert!(execute_command(net, Command::Create(["gen", "gen"])).is_ok());
+        assert_eq!(
+            net.get_component("gen").unwrap().r#type(),
+            ComponentType::Generator
+        );
+        assert!(execute_command(net, Command::Create(["grid", "grid"])).is_ok());
+        assert_eq!(
+            net.get_component("grid").unwrap().r#type(),
+            ComponentType::ExternalGrid
+        );
 
         assert!(execute_command(net, Command::Create(["node", "6"])).is_ok());
         assert_eq!(net.get_node("6").unwrap().name(), "6");
@@ -452,7 +1164,8 @@ mod tests {
             .unwrap()
             .terminal(0)
             .unwrap()
-            .borrow()
+            .read()
+            .unwrap()
             .get_node()
             .is_ok());
 
@@ -464,7 +1177,8 @@ mod tests {
             .unwrap()
             .terminal(0)
             .unwrap()
-            .borrow()
+            .read()
+            .unwrap()
             .get_node()
             .is_err());
 
@@ -477,7 +1191,8 @@ mod tests {
             .unwrap()
             .position()
             .unwrap()
-            .borrow()
+            .read()
+            .unwrap()
             .is_open());
         assert!(execute_command(net, Command::Open("1")).is_err());
         assert!(execute_command(net, Command::Close("1")).is_ok());
@@ -486,7 +1201,8 @@ mod tests {
             .unwrap()
             .position()
             .unwrap()
-            .borrow()
+            .read()
+            .unwrap()
             .is_closed());
         assert!(execute_command(net, Command::Close("1")).is_err());
         assert!(execute_command(net, Command::Open("1")).is_ok());
@@ -495,7 +1211,8 @@ mod tests {
             .unwrap()
             .position()
             .unwrap()
-            .borrow()
+            .read()
+            .unwrap()
             .is_open());
 
         // Update
@@ -506,5 +1223,192 @@ mod tests {
         assert_eq!(net.get_component("4").unwrap().value().unwrap(), 618.581);
         assert!(execute_command(net, Command::Update(["4", "5"])).is_ok());
         assert_eq!(net.get_component("4").unwrap().value().unwrap(), 5f64);
+
+        // Busbar / Coupler
+        assert!(execute_command(net, Command::Busbar(["bb", "x", "2"])).is_err());
+        assert!(execute_command(net, Command::Busbar(["bb", "110", "-2"])).is_err());
+        assert!(execute_command(net, Command::Busbar(["bb", "110", "2"])).is_ok());
+        assert_eq!(net.busbar("bb").unwrap().sections().len(), 2);
+        assert!(execute_command(net, Command::Coupler(["bc", "bb.1", "6"])).is_err());
+        assert!(execute_command(net, Command::Coupler(["bc", "bb.1", "bb.2"])).is_ok());
+        assert_eq!(
+            net.get_component("bc").unwrap().r#type(),
+            ComponentType::CircuitBreaker
+        );
+        assert!(execute_command(net, Command::List()).is_ok());
+
+        // Set
+        assert!(execute_command(net, Command::Set(["line", "length", "x"])).is_err());
+        assert!(execute_command(net, Command::Set(["line", "ratio", "1"])).is_err());
+        assert!(execute_command(net, Command::Set(["4", "length", "1"])).is_err());
+        assert!(execute_command(net, Command::Set(["line", "length", "35.2"])).is_ok());
+        assert_eq!(
+            net.get_component("line")
+                .unwrap()
+                .parameter("length")
+                .unwrap(),
+            35.2
+        );
+        assert!(execute_command(net, Command::Show("line")).is_ok());
+        assert!(execute_command(net, Command::Set(["gen", "v", "1.05"])).is_ok());
+        assert!(execute_command(net, Command::Set(["grid", "sk", "-1"])).is_err());
+
+        // Vector
+        assert!(execute_command(net, Command::Vector(["5", "YNyn0d5"])).is_err());
+        assert!(execute_command(net, Command::Vector(["5", "dyn11"])).is_err());
+        assert!(execute_command(net, Command::Vector(["tf3", "YNyn0d5"])).is_ok());
+        assert!(execute_command(net, Command::Set(["tf3", "uk23", "7.5"])).is_ok());
+        assert!(execute_command(net, Command::Show("tf3")).is_ok());
+
+        // Tap changer
+        assert!(execute_command(net, Command::TapUp("tf3")).is_ok());
+        assert!(execute_command(net, Command::Set(["tf3", "tap_step", "0"])).is_err());
+        assert!(execute_command(net, Command::Set(["tf3", "tap_min", "0"])).is_ok());
+        assert!(execute_command(net, Command::TapDown("tf3")).is_ok());
+        assert!(execute_command(net, Command::TapDown("tf3")).is_err());
+        assert!(execute_command(net, Command::TapUp("4")).is_err());
+        assert!(execute_command(net, Command::Show("tf3")).is_ok());
+
+        // Model
+        assert!(execute_command(net, Command::Model(["load", "constant-current"])).is_err());
+        assert!(execute_command(net, Command::Model(["gen", "constant-power"])).is_err());
+        assert!(execute_command(net, Command::Model(["load", "Constant-Impedance"])).is_ok());
+        assert_eq!(
+            net.get_component("load")
+                .unwrap()
+                .load_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .model(),
+            LoadModel::ConstantImpedance
+        );
+        assert!(execute_command(net, Command::Show("load")).is_ok());
+
+        // Report
+        assert!(execute_command(net, Command::Report(["4", "open"])).is_err());
+        assert!(execute_command(net, Command::Report(["1", "ajar"])).is_err());
+        assert!(execute_command(net, Command::Report(["1", "Bad"])).is_ok());
+        assert!(execute_command(net, Command::Report(["1", "intermediate"])).is_err());
+        assert!(execute_command(net, Command::Report(["1", "open"])).is_ok());
+        assert!(net
+            .get_component("1")
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_open());
+
+        // Operating time / Advance
+        assert!(execute_command(net, Command::OperatingTime(["4", "1"])).is_err());
+        assert!(execute_command(net, Command::OperatingTime(["1", "-1"])).is_err());
+        assert!(execute_command(net, Command::OperatingTime(["1", "0.5"])).is_ok());
+        assert!(execute_command(net, Command::Close("1")).is_ok());
+        assert_eq!(net.operations().len(), 1);
+        assert!(execute_command(net, Command::Advance("soon")).is_err());
+        assert!(execute_command(net, Command::Advance("1e20")).is_err());
+        assert!(execute_command(net, Command::Advance("1e19")).is_ok());
+        assert!(execute_command(net, Command::Advance("1e19")).is_err());
+        assert!(net.undo().is_ok());
+        assert!(execute_command(net, Command::OperatingTime(["1", "inf"])).is_err());
+        assert!(execute_command(net, Command::Advance("0.2")).is_ok());
+        assert_eq!(net.operations().len(), 1);
+        assert!(execute_command(net, Command::Advance("0.3")).is_ok());
+        assert!(net.operations().is_empty());
+        assert!(execute_command(net, Command::Open("1")).is_ok());
+        assert!(execute_command(net, Command::Advance("1")).is_ok());
+        assert!(execute_command(net, Command::OperatingTime(["1", "0"])).is_ok());
+
+        // Tags
+        assert!(execute_command(net, Command::TagAdd(["1", "ajar", "T1"])).is_err());
+        assert!(
+            execute_command(net, Command::TagAdd(["i dont exist", "locked-open", "T1"])).is_err()
+        );
+        assert!(execute_command(net, Command::TagList(None)).is_ok());
+        assert!(execute_command(net, Command::TagAdd(["1", "Locked-Open", "T1"])).is_ok());
+        assert!(execute_command(net, Command::TagList(Some("1"))).is_ok());
+        assert!(execute_command(net, Command::TagList(Some("i dont exist"))).is_err());
+        assert!(matches!(
+            execute_command(net, Command::Close("1")),
+            Err(Error::Tagged { .. })
+        ));
+        assert!(execute_command(net, Command::TagRemove(["1", "T2"])).is_err());
+        assert!(execute_command(net, Command::TagRemove(["1", "T1"])).is_ok());
+        assert!(net.tags("1").unwrap().is_empty());
+        assert!(net
+            .get_component("1")
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_open());
+
+        // Source / State
+        assert!(execute_command(net, Command::Source("1")).is_err());
+        assert!(execute_command(net, Command::Source("6")).is_ok());
+        assert!(execute_command(net, Command::Source("6")).is_err());
+        assert!(execute_command(net, Command::State()).is_ok());
+
+        // Interlocks
+        assert!(execute_command(net, Command::Interlocks("4")).is_err());
+        assert!(execute_command(net, Command::Interlocks("1")).is_ok());
+        net.add_interlock(Interlock::BreakerOpen {
+            switch: "2".to_string(),
+            breaker: "1".to_string(),
+        })
+        .unwrap();
+        assert!(execute_command(net, Command::Interlocks("2")).is_ok());
+        assert!(execute_command(net, Command::Close("1")).is_ok());
+        assert!(execute_command(net, Command::Close("2")).is_err());
+        assert!(execute_command(net, Command::Open("1")).is_ok());
+
+        // Isolate
+        assert!(execute_command(net, Command::Isolate("i dont exist")).is_err());
+        assert!(execute_command(net, Command::Isolate("4")).is_ok());
+
+        // Dry run / Run
+        let path = std::env::temp_dir().join("hvnv_cli_test_sequence.txt");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "close 1\nopen 1\n").unwrap();
+        assert!(execute_command(net, Command::DryRun("i dont exist.txt")).is_err());
+        assert!(execute_command(net, Command::DryRun(path)).is_ok());
+        assert!(execute_command(net, Command::Run(path)).is_ok());
+        std::fs::write(path, "open 1\n").unwrap();
+        assert!(execute_command(net, Command::DryRun(path)).is_ok());
+        assert!(execute_command(net, Command::Run(path)).is_err());
+        std::fs::remove_file(path).unwrap();
+
+        // Undo / Redo
+        assert!(execute_command(net, Command::Delete("6")).is_ok());
+        assert!(net.get_node("6").is_err());
+        assert!(execute_command(net, Command::Undo).is_ok());
+        assert!(net.get_node("6").is_ok());
+        assert!(execute_command(net, Command::Redo).is_ok());
+        assert!(execute_command(net, Command::Redo).is_err());
+        assert!(execute_command(net, Command::Undo).is_ok());
+        assert_eq!(net.source_ids(), vec![net.node_id("6").unwrap()]);
+
+        // Journal / Export
+        assert!(execute_command(net, Command::Journal(None)).is_ok());
+        assert!(execute_command(net, Command::Journal(Some("1"))).is_ok());
+        let path = std::env::temp_dir().join("hvnv_cli_test_journal.csv");
+        let path = path.to_str().unwrap();
+        assert!(execute_command(net, Command::Export(path)).is_ok());
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .starts_with("time,sim_time,source,kind,name,description\n"));
+        std::fs::remove_file(path).unwrap();
+
+        // Save / Load
+        let path = std::env::temp_dir().join("hvnv_cli_test_network.json");
+        let path = path.to_str().unwrap();
+        assert!(execute_command(net, Command::Save(path)).is_ok());
+        assert!(execute_command(net, Command::Load("i dont exist.json")).is_err());
+        assert!(execute_command(net, Command::Delete("6")).is_ok());
+        assert!(execute_command(net, Command::Load(path)).is_ok());
+        assert!(net.get_node("6").is_ok());
+        std::fs::remove_file(path).unwrap();
     }
 }
@@ -1,14 +1,19 @@
-use std::cell::RefCell;
 use std::fmt;
-use std::rc::Rc;
+use std::sync::RwLock;
 
+use super::error::Error;
 use super::measurement::Measurement;
-use super::node::Node;
+use super::network::NodeId;
+use super::parameters::{
+    ExternalGridParameters, GeneratorParameters, LineParameters, LoadParameters,
+    TransformerParameters,
+};
 use super::position::SwitchgearPosition;
+use super::tag::Tags;
 use super::terminal::Terminal;
 
 /// Component Type
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ComponentType {
     /// Circuit Breaker
     CircuitBreaker,
@@ -18,26 +23,43 @@ pub enum ComponentType {
     EarthingSwitch,
     /// Voltage Transformer
     VoltageTransformer,
-    /// Transformer
+    /// Two-Winding Transformer
     Transformer,
+    /// Three-Winding Transformer
+    ThreeWindingTransformer,
+    /// Overhead Line
+    Line,
+    /// Cable
+    Cable,
+    /// Load
+    Load,
+    /// Generator
+    Generator,
+    /// External Grid
+    ExternalGrid,
 }
 
 impl fmt::Display for ComponentType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let typestr: &str;
-        match self {
-            ComponentType::CircuitBreaker => typestr = "CircuitBreaker",
-            ComponentType::Disconnector => typestr = "Disconnector",
-            ComponentType::EarthingSwitch => typestr = "Earthing Switch",
-            ComponentType::VoltageTransformer => typestr = "Voltage Transformer",
-            ComponentType::Transformer => typestr = "Transformer",
-        }
+        let typestr = match self {
+            ComponentType::CircuitBreaker => "CircuitBreaker",
+            ComponentType::Disconnector => "Disconnector",
+            ComponentType::EarthingSwitch => "Earthing Switch",
+            ComponentType::VoltageTransformer => "Voltage Transformer",
+            ComponentType::Transformer => "Transformer",
+            ComponentType::ThreeWindingTransformer => "Three-Winding Transformer",
+            ComponentType::Line => "Line",
+            ComponentType::Cable => "Cable",
+            ComponentType::Load => "Load",
+            ComponentType::Generator => "Generator",
+            ComponentType::ExternalGrid => "External Grid",
+        };
         write!(f, "{}", typestr)
     }
 }
 
 /// Trait to define components. Each component should have a [ComponentType] and at least one [Terminal]
-pub trait Component {
+pub trait Component: Send + Sync {
     /// Constructor; sets the component name
     fn new(name: &str) -> Self
     where
@@ -48,100 +70,96 @@ pub trait Component {
     /// Returns the name of the component
     fn name(&self) -> &String;
     /// Returns the terminal with a given index, or an error if the component has less terminals than the given index.
-    fn terminal(&self, index: usize) -> Result<&RefCell<Terminal>, String>;
-
-    /// Connect the component to a node on a given terminal index
-    ///
-    /// Only allow a connection if
-    /// * The given node is not already connected to another terminal
-    /// * The given terminal index exists (the component has less terminals than the given index)
-    /// * The given terminal is not already connected to another node
-    fn connect(&self, node: Rc<Node>, terminal_index: usize) -> Result<(), String> {
-        let mut i = 0;
-        loop {
-            if i == terminal_index {
-                i += 1;
-                continue;
-            }
-            match self.terminal(i) {
-                Err(_) => {
-                    break;
-                }
-                Ok(t) => {
-                    if let Ok(n) = t.borrow().get_node() {
-                        if Rc::ptr_eq(&node, &n) {
-                            return Err(format!(
-                                "Component {} is already connected to node {} on terminal {}",
-                                self.name(),
-                                node.name(),
-                                i
-                            ));
-                        }
-                    }
-                }
-            }
-            i += 1;
-        }
-        let t = self.terminal(terminal_index)?;
-        t.borrow_mut().connect(node)?;
-        Ok(())
-    }
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error>;
 
-    /// Disconnect the component from the given node. Returns an error if not connected to it.
-    fn disconnect(&self, node: Rc<Node>) -> Result<(), String> {
+    /// Returns the id of each node connected to one of the component terminals, see [Network::connect](super::network::Network::connect)
+    fn nodes(&self) -> Vec<NodeId> {
+        let mut nodes = vec![];
         let mut i = 0;
         while let Ok(t) = self.terminal(i) {
-            let mut t = t.borrow_mut();
-            if let Ok(n) = t.get_node() {
-                if Rc::ptr_eq(&n, &node) {
-                    return t.disconnect();
-                }
+            if let Ok(n) = t.read().unwrap().get_node() {
+                nodes.push(n);
             }
             i += 1;
         }
-        Err(format!(
-            "Component {} is not connnected to node {}",
-            self.name(),
-            node.name()
-        ))
+        nodes
     }
 
+    /// Returns the safety [Tags](super::tag::Tag) placed on the component
+    fn tags(&self) -> &RwLock<Tags>;
+
     /// Get component [SwitchgearPosition], only implemented for switchgear
-    fn position(&self) -> Result<&RefCell<SwitchgearPosition>, String> {
-        Err(format!(
-            "Components of type {} have no position",
-            self.r#type()
-        ))
+    fn position(&self) -> Result<&RwLock<SwitchgearPosition>, Error> {
+        Err(Error::NoPosition(self.name().clone()))
     }
 
-    /// Open switchgear
-    fn open(&self) -> Result<(), String> {
+    /// Open switchgear, if it is not tagged
+    fn open(&self) -> Result<(), Error> {
         let pos = self.position()?;
-        pos.borrow_mut().open()?;
+        self.tags().read().unwrap().check(self.name())?;
+        pos.write().unwrap().open()?;
         Ok(())
     }
 
-    /// Close switchgear
-    fn close(&self) -> Result<(), String> {
+    /// Close switchgear, if it is not tagged
+    fn close(&self) -> Result<(), Error> {
         let pos = self.position()?;
-        pos.borrow_mut().close()?;
+        self.tags().read().unwrap().check(self.name())?;
+        pos.write().unwrap().close()?;
         Ok(())
     }
 
+    /// Get component [LineParameters], only implemented for lines and cables
+    fn line_parameters(&self) -> Result<&RwLock<LineParameters>, Error> {
+        Err(Error::NoLineParameters(self.name().clone()))
+    }
+
+    /// Get component [TransformerParameters], only implemented for transformers
+    fn transformer_parameters(&self) -> Result<&RwLock<TransformerParameters>, Error> {
+        Err(Error::NoTransformerParameters(self.name().clone()))
+    }
+
+    /// Get component [LoadParameters], only implemented for loads
+    fn load_parameters(&self) -> Result<&RwLock<LoadParameters>, Error> {
+        Err(Error::NoLoadParameters(self.name().clone()))
+    }
+
+    /// Get component [GeneratorParameters], only implemented for generators
+    fn generator_parameters(&self) -> Result<&RwLock<GeneratorParameters>, Error> {
+        Err(Error::NoGeneratorParameters(self.name().clone()))
+    }
+
+    /// Get component [ExternalGridParameters], only implemented for external grids
+    fn external_grid_parameters(&self) -> Result<&RwLock<ExternalGridParameters>, Error> {
+        Err(Error::NoExternalGridParameters(self.name().clone()))
+    }
+
+    /// Returns the name and value of each electrical parameter of the component
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![]
+    }
+
+    /// Get an electrical parameter by name
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        Err(Error::NotFound {
+            kind: "parameter",
+            name: format!("{} of component {}", name, self.name()),
+        })
+    }
+
+    /// Set an electrical parameter by name
+    fn set_parameter(&self, name: &str, _value: f64) -> Result<(), Error> {
+        self.parameter(name).map(|_| ())
+    }
+
     /// Update measurement value
-    fn update(&self, _value: f64) -> Result<(), String> {
-        Err(format!(
-            "Components of type {} have no measurement",
-            self.r#type()
-        ))
+    fn update(&self, _value: f64) -> Result<(), Error> {
+        Err(Error::NoMeasurement(self.name().clone()))
     }
 
     /// Get measurement value
-    fn value(&self) -> Result<f64, String> {
-        Err(format!(
-            "Components of type {} have no measurement",
-            self.r#type()
-        ))
+    fn value(&self) -> Result<f64, Error> {
+        Err(Error::NoMeasurement(self.name().clone()))
     }
 }
 
@@ -154,16 +172,18 @@ impl fmt::Display for dyn Component {
 /// Circuit Breaker
 pub struct CircuitBreaker {
     name: String,
-    position: RefCell<SwitchgearPosition>,
-    terminals: [RefCell<Terminal>; 2],
+    tags: RwLock<Tags>,
+    position: RwLock<SwitchgearPosition>,
+    terminals: [RwLock<Terminal>; 2],
 }
 
 impl Component for CircuitBreaker {
     fn new(name: &str) -> CircuitBreaker {
         CircuitBreaker {
             name: name.to_string(),
-            position: RefCell::new(SwitchgearPosition::new()),
-            terminals: [RefCell::new(Terminal::new()), RefCell::new(Terminal::new())],
+            tags: RwLock::new(Tags::new()),
+            position: RwLock::new(SwitchgearPosition::new()),
+            terminals: [RwLock::new(Terminal::new()), RwLock::new(Terminal::new())],
         }
     }
 
@@ -175,14 +195,22 @@ impl Component for CircuitBreaker {
         &self.name
     }
 
-    fn position(&self) -> Result<&RefCell<SwitchgearPosition>, String> {
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn position(&self) -> Result<&RwLock<SwitchgearPosition>, Error> {
         Ok(&self.position)
     }
 
-    fn terminal(&self, index: usize) -> Result<&RefCell<Terminal>, String> {
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
         match self.terminals.get(index) {
             Some(t) => Ok(t),
-            None => Err(format!("Component {} of type {:?} does not have a terminal with index {}; it only has {} terminals", self.name, self.r#type(), index, self.terminals.len())),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
         }
     }
 }
@@ -190,16 +218,18 @@ impl Component for CircuitBreaker {
 /// Disconnector
 pub struct Disconnector {
     name: String,
-    position: RefCell<SwitchgearPosition>,
-    terminals: [RefCell<Terminal>; 2],
+    tags: RwLock<Tags>,
+    position: RwLock<SwitchgearPosition>,
+    terminals: [RwLock<Terminal>; 2],
 }
 
 impl Component for Disconnector {
     fn new(name: &str) -> Disconnector {
         Disconnector {
             name: name.to_string(),
-            position: RefCell::new(SwitchgearPosition::new()),
-            terminals: [RefCell::new(Terminal::new()), RefCell::new(Terminal::new())],
+            tags: RwLock::new(Tags::new()),
+            position: RwLock::new(SwitchgearPosition::new()),
+            terminals: [RwLock::new(Terminal::new()), RwLock::new(Terminal::new())],
         }
     }
 
@@ -211,14 +241,22 @@ impl Component for Disconnector {
         &self.name
     }
 
-    fn position(&self) -> Result<&RefCell<SwitchgearPosition>, String> {
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn position(&self) -> Result<&RwLock<SwitchgearPosition>, Error> {
         Ok(&self.position)
     }
 
-    fn terminal(&self, index: usize) -> Result<&RefCell<Terminal>, String> {
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
         match self.terminals.get(index) {
             Some(t) => Ok(t),
-            None => Err(format!("Component {} of type {:?} does not have a terminal with index {}; it only has {} terminals", self.name, self.r#type(), index, self.terminals.len())),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
         }
     }
 }
@@ -226,16 +264,18 @@ impl Component for Disconnector {
 /// Earthing Switch
 pub struct EarthingSwitch {
     name: String,
-    position: RefCell<SwitchgearPosition>,
-    terminals: [RefCell<Terminal>; 1],
+    tags: RwLock<Tags>,
+    position: RwLock<SwitchgearPosition>,
+    terminals: [RwLock<Terminal>; 1],
 }
 
 impl Component for EarthingSwitch {
     fn new(name: &str) -> EarthingSwitch {
         EarthingSwitch {
             name: name.to_string(),
-            position: RefCell::new(SwitchgearPosition::new()),
-            terminals: [RefCell::new(Terminal::new()); 1],
+            tags: RwLock::new(Tags::new()),
+            position: RwLock::new(SwitchgearPosition::new()),
+            terminals: [RwLock::new(Terminal::new()); 1],
         }
     }
 
@@ -247,14 +287,22 @@ impl Component for EarthingSwitch {
         &self.name
     }
 
-    fn terminal(&self, index: usize) -> Result<&RefCell<Terminal>, String> {
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
         match self.terminals.get(index) {
             Some(t) => Ok(t),
-            None => Err(format!("Component {} of type {:?} does not have a terminal with index {}; it only has {} terminals", self.name, self.r#type(), index, self.terminals.len())),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
         }
     }
 
-    fn position(&self) -> Result<&RefCell<SwitchgearPosition>, String> {
+    fn position(&self) -> Result<&RwLock<SwitchgearPosition>, Error> {
         Ok(&self.position)
     }
 }
@@ -262,16 +310,18 @@ impl Component for EarthingSwitch {
 /// Voltage Transformer
 pub struct VoltageTransformer {
     name: String,
-    measurement: RefCell<Measurement>,
-    terminals: [RefCell<Terminal>; 1],
+    tags: RwLock<Tags>,
+    measurement: RwLock<Measurement>,
+    terminals: [RwLock<Terminal>; 1],
 }
 
 impl Component for VoltageTransformer {
     fn new(name: &str) -> VoltageTransformer {
         VoltageTransformer {
             name: name.to_string(),
-            measurement: RefCell::new(Measurement::new()),
-            terminals: [RefCell::new(Terminal::new())],
+            tags: RwLock::new(Tags::new()),
+            measurement: RwLock::new(Measurement::new()),
+            terminals: [RwLock::new(Terminal::new())],
         }
     }
 
@@ -283,55 +333,467 @@ impl Component for VoltageTransformer {
         &self.name
     }
 
-    fn terminal(&self, index: usize) -> Result<&RefCell<Terminal>, String> {
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
         match self.terminals.get(index) {
             Some(t) => Ok(t),
-            None => Err(format!("Component {} of type {:?} does not have a terminal with index {}; it only has {} terminals", self.name, self.r#type(), index, self.terminals.len())),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
         }
     }
 
-    fn update(&self, value: f64) -> Result<(), String> {
-        self.measurement.borrow_mut().update(value);
+    fn update(&self, value: f64) -> Result<(), Error> {
+        self.measurement.write().unwrap().update(value);
         Ok(())
     }
 
-    fn value(&self) -> Result<f64, String> {
-        Ok(self.measurement.borrow().value())
+    fn value(&self) -> Result<f64, Error> {
+        Ok(self.measurement.read().unwrap().value())
     }
 }
 
-/// Transformer
+/// Two-Winding Transformer; terminal 0 is the high voltage winding
 pub struct Transformer {
     name: String,
-    terminals: [RefCell<Terminal>; 3],
+    tags: RwLock<Tags>,
+    parameters: RwLock<TransformerParameters>,
+    terminals: [RwLock<Terminal>; 2],
 }
 
 impl Component for Transformer {
     fn new(name: &str) -> Transformer {
         Transformer {
             name: name.to_string(),
+            tags: RwLock::new(Tags::new()),
+            parameters: RwLock::new(TransformerParameters::two_winding()),
+            terminals: [RwLock::new(Terminal::new()), RwLock::new(Terminal::new())],
+        }
+    }
+
+    fn r#type(&self) -> ComponentType {
+        ComponentType::Transformer
+    }
+
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
+        match self.terminals.get(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
+        }
+    }
+
+    fn transformer_parameters(&self) -> Result<&RwLock<TransformerParameters>, Error> {
+        Ok(&self.parameters)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let p = self.parameters.read().unwrap();
+        p.names()
+            .iter()
+            .map(|name| (*name, p.get(name).unwrap()))
+            .collect()
+    }
+
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        self.parameters.read().unwrap().get(name)
+    }
+
+    fn set_parameter(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.parameters.write().unwrap().set(name, value)
+    }
+}
+
+/// Three-Winding Transformer; terminal 0 is the high voltage winding
+pub struct ThreeWindingTransformer {
+    name: String,
+    tags: RwLock<Tags>,
+    parameters: RwLock<TransformerParameters>,
+    terminals: [RwLock<Terminal>; 3],
+}
+
+impl Component for ThreeWindingTransformer {
+    fn new(name: &str) -> ThreeWindingTransformer {
+        ThreeWindingTransformer {
+            name: name.to_string(),
+            tags: RwLock::new(Tags::new()),
+            parameters: RwLock::new(TransformerParameters::three_winding()),
             terminals: [
-                RefCell::new(Terminal::new()),
-                RefCell::new(Terminal::new()),
-                RefCell::new(Terminal::new()),
+                RwLock::new(Terminal::new()),
+                RwLock::new(Terminal::new()),
+                RwLock::new(Terminal::new()),
             ],
         }
     }
 
     fn r#type(&self) -> ComponentType {
-        ComponentType::Transformer
+        ComponentType::ThreeWindingTransformer
+    }
+
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
+        match self.terminals.get(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
+        }
+    }
+
+    fn transformer_parameters(&self) -> Result<&RwLock<TransformerParameters>, Error> {
+        Ok(&self.parameters)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let p = self.parameters.read().unwrap();
+        p.names()
+            .iter()
+            .map(|name| (*name, p.get(name).unwrap()))
+            .collect()
+    }
+
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        self.parameters.read().unwrap().get(name)
+    }
+
+    fn set_parameter(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.parameters.write().unwrap().set(name, value)
+    }
+}
+
+/// Overhead Line
+pub struct Line {
+    name: String,
+    tags: RwLock<Tags>,
+    parameters: RwLock<LineParameters>,
+    terminals: [RwLock<Terminal>; 2],
+}
+
+impl Component for Line {
+    fn new(name: &str) -> Line {
+        Line {
+            name: name.to_string(),
+            tags: RwLock::new(Tags::new()),
+            parameters: RwLock::new(LineParameters::default()),
+            terminals: [RwLock::new(Terminal::new()), RwLock::new(Terminal::new())],
+        }
+    }
+
+    fn r#type(&self) -> ComponentType {
+        ComponentType::Line
+    }
+
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
+        match self.terminals.get(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
+        }
+    }
+
+    fn line_parameters(&self) -> Result<&RwLock<LineParameters>, Error> {
+        Ok(&self.parameters)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let p = self.parameters.read().unwrap();
+        LineParameters::NAMES
+            .iter()
+            .map(|name| (*name, p.get(name).unwrap()))
+            .collect()
+    }
+
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        self.parameters.read().unwrap().get(name)
+    }
+
+    fn set_parameter(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.parameters.write().unwrap().set(name, value)
+    }
+}
+
+/// Cable
+pub struct Cable {
+    name: String,
+    tags: RwLock<Tags>,
+    parameters: RwLock<LineParameters>,
+    terminals: [RwLock<Terminal>; 2],
+}
+
+impl Component for Cable {
+    fn new(name: &str) -> Cable {
+        Cable {
+            name: name.to_string(),
+            tags: RwLock::new(Tags::new()),
+            parameters: RwLock::new(LineParameters::default()),
+            terminals: [RwLock::new(Terminal::new()), RwLock::new(Terminal::new())],
+        }
+    }
+
+    fn r#type(&self) -> ComponentType {
+        ComponentType::Cable
+    }
+
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
+        match self.terminals.get(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
+        }
+    }
+
+    fn line_parameters(&self) -> Result<&RwLock<LineParameters>, Error> {
+        Ok(&self.parameters)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let p = self.parameters.read().unwrap();
+        LineParameters::NAMES
+            .iter()
+            .map(|name| (*name, p.get(name).unwrap()))
+            .collect()
+    }
+
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        self.parameters.read().unwrap().get(name)
+    }
+
+    fn set_parameter(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.parameters.write().unwrap().set(name, value)
+    }
+}
+
+/// Load; consumes power
+pub struct Load {
+    name: String,
+    tags: RwLock<Tags>,
+    parameters: RwLock<LoadParameters>,
+    terminals: [RwLock<Terminal>; 1],
+}
+
+impl Component for Load {
+    fn new(name: &str) -> Load {
+        Load {
+            name: name.to_string(),
+            tags: RwLock::new(Tags::new()),
+            parameters: RwLock::new(LoadParameters::default()),
+            terminals: [RwLock::new(Terminal::new())],
+        }
+    }
+
+    fn r#type(&self) -> ComponentType {
+        ComponentType::Load
+    }
+
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
+        match self.terminals.get(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
+        }
+    }
+
+    fn load_parameters(&self) -> Result<&RwLock<LoadParameters>, Error> {
+        Ok(&self.parameters)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let p = self.parameters.read().unwrap();
+        LoadParameters::NAMES
+            .iter()
+            .map(|name| (*name, p.get(name).unwrap()))
+            .collect()
+    }
+
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        self.parameters.read().unwrap().get(name)
+    }
+
+    fn set_parameter(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.parameters.write().unwrap().set(name, value)
+    }
+}
+
+/// Generator; injects power
+pub struct Generator {
+    name: String,
+    tags: RwLock<Tags>,
+    parameters: RwLock<GeneratorParameters>,
+    terminals: [RwLock<Terminal>; 1],
+}
+
+impl Component for Generator {
+    fn new(name: &str) -> Generator {
+        Generator {
+            name: name.to_string(),
+            tags: RwLock::new(Tags::new()),
+            parameters: RwLock::new(GeneratorParameters::default()),
+            terminals: [RwLock::new(Terminal::new())],
+        }
+    }
+
+    fn r#type(&self) -> ComponentType {
+        ComponentType::Generator
+    }
+
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
+        match self.terminals.get(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
+        }
+    }
+
+    fn generator_parameters(&self) -> Result<&RwLock<GeneratorParameters>, Error> {
+        Ok(&self.parameters)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let p = self.parameters.read().unwrap();
+        GeneratorParameters::NAMES
+            .iter()
+            .map(|name| (*name, p.get(name).unwrap()))
+            .collect()
+    }
+
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        self.parameters.read().unwrap().get(name)
+    }
+
+    fn set_parameter(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.parameters.write().unwrap().set(name, value)
+    }
+}
+
+/// External Grid; infeed from the grid beyond the network
+pub struct ExternalGrid {
+    name: String,
+    tags: RwLock<Tags>,
+    parameters: RwLock<ExternalGridParameters>,
+    terminals: [RwLock<Terminal>; 1],
+}
+
+impl Component for ExternalGrid {
+    fn new(name: &str) -> ExternalGrid {
+        ExternalGrid {
+            name: name.to_string(),
+            tags: RwLock::new(Tags::new()),
+            parameters: RwLock::new(ExternalGridParameters::default()),
+            terminals: [RwLock::new(Terminal::new())],
+        }
+    }
+
+    fn r#type(&self) -> ComponentType {
+        ComponentType::ExternalGrid
     }
 
     fn name(&self) -> &String {
         &self.name
     }
 
-    fn terminal(&self, index: usize) -> Result<&RefCell<Terminal>, String> {
+    fn tags(&self) -> &RwLock<Tags> {
+        &self.tags
+    }
+
+    fn terminal(&self, index: usize) -> Result<&RwLock<Terminal>, Error> {
         match self.terminals.get(index) {
             Some(t) => Ok(t),
-            None => Err(format!("Component {} of type {:?} does not have a terminal with index {}; it only has {} terminals", self.name, self.r#type(), index, self.terminals.len())),
+            None => Err(Error::InvalidTerminal {
+                component: self.name.clone(),
+                index,
+                count: self.terminals.len(),
+            }),
         }
     }
+
+    fn external_grid_parameters(&self) -> Result<&RwLock<ExternalGridParameters>, Error> {
+        Ok(&self.parameters)
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        let p = self.parameters.read().unwrap();
+        ExternalGridParameters::NAMES
+            .iter()
+            .map(|name| (*name, p.get(name).unwrap()))
+            .collect()
+    }
+
+    fn parameter(&self, name: &str) -> Result<f64, Error> {
+        self.parameters.read().unwrap().get(name)
+    }
+
+    fn set_parameter(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.parameters.write().unwrap().set(name, value)
+    }
 }
 
 #[cfg(test)]
@@ -384,37 +846,29 @@ mod tests {
         assert_eq!(ds.terminals.len(), 2);
         assert_eq!(es.terminals.len(), 1);
         assert_eq!(vt.terminals.len(), 1);
-        assert_eq!(tf.terminals.len(), 3);
+        assert_eq!(tf.terminals.len(), 2);
+        assert_eq!(ThreeWindingTransformer::new("tf3").terminals.len(), 3);
 
         assert!(cb.terminal(2).is_err());
         assert!(ds.terminal(2).is_err());
         assert!(es.terminal(1).is_err());
         assert!(vt.terminal(1).is_err());
-        assert!(tf.terminal(3).is_err());
-    }
-
-    #[test]
-    fn component_connect() {
-        let n = Rc::new(Node::new("node"));
-        let n2 = Rc::new(Node::new("node2"));
-        let cb = CircuitBreaker::new("cb");
-
-        assert!(cb.disconnect(n.clone()).is_err());
-        assert!(cb.connect(n.clone(), 2).is_err());
-        assert!(cb.connect(n.clone(), 0).is_ok());
-        assert!(cb.connect(n.clone(), 0).is_err());
-        assert!(cb.connect(n.clone(), 1).is_err());
-        assert!(cb.connect(n2.clone(), 1).is_ok());
-        assert!(cb.disconnect(n2.clone()).is_ok());
-        assert!(cb.connect(n2.clone(), 0).is_err());
+        assert!(matches!(
+            tf.terminal(2),
+            Err(Error::InvalidTerminal {
+                index: 2,
+                count: 2,
+                ..
+            })
+        ));
     }
 
     #[test]
     fn component_openclose() {
         let (cb, ds, es, vt, tf) = create_test_components();
 
-        assert!(vt.close().is_err());
-        assert!(tf.close().is_err());
+        assert!(matches!(vt.close(), Err(Error::NoPosition(_))));
+        assert!(matches!(tf.close(), Err(Error::NoPosition(_))));
 
         assert!(cb.open().is_err());
         assert!(cb.close().is_ok());
@@ -432,11 +886,143 @@ mod tests {
         assert!(es.open().is_ok());
     }
 
+    #[test]
+    fn component_tags() {
+        use crate::tag::{Tag, TagKind};
+        let (cb, _, _, vt, _) = create_test_components();
+
+        cb.tags()
+            .write()
+            .unwrap()
+            .add(Tag::new("T1", TagKind::DoNotOperate))
+            .unwrap();
+        assert!(matches!(cb.close(), Err(Error::Tagged { .. })));
+        assert!(cb.position().unwrap().read().unwrap().is_open());
+        cb.tags().write().unwrap().remove("T1").unwrap();
+        assert!(cb.close().is_ok());
+
+        vt.tags()
+            .write()
+            .unwrap()
+            .add(Tag::new("T2", TagKind::OutOfService))
+            .unwrap();
+        assert!(vt.update(1.0).is_ok());
+    }
+
+    #[test]
+    fn component_line_parameters() {
+        let (cb, _, _, vt, _) = create_test_components();
+        let line = Line::new("line");
+        let cable = Cable::new("cable");
+
+        assert_eq!(line.r#type(), ComponentType::Line);
+        assert_eq!(cable.r#type(), ComponentType::Cable);
+        assert_eq!(cable.terminals.len(), 2);
+        assert!(line.close().is_err());
+
+        line.set_parameter("rating", 1200.0).unwrap();
+        assert_eq!(line.parameter("rating").unwrap(), 1200.0);
+        assert_eq!(
+            line.line_parameters().unwrap().read().unwrap().rating(),
+            1200.0
+        );
+        assert_eq!(line.parameters().len(), 5);
+        assert!(cable.set_parameter("rating", -1.0).is_err());
+        assert!(cable.set_parameter("ratio", 1.0).is_err());
+
+        assert!(matches!(
+            cb.line_parameters(),
+            Err(Error::NoLineParameters(_))
+        ));
+        assert!(vt.parameters().is_empty());
+        assert!(vt.set_parameter("r", 1.0).is_err());
+    }
+
+    #[test]
+    fn component_transformer_parameters() {
+        let (_, _, _, vt, tf) = create_test_components();
+        let tf3 = ThreeWindingTransformer::new("tf3");
+
+        assert_eq!(tf3.r#type(), ComponentType::ThreeWindingTransformer);
+        assert_eq!(tf.parameters().len(), 8);
+        assert_eq!(tf3.parameters().len(), 11);
+        tf.set_parameter("u1", 380.0).unwrap();
+        assert_eq!(
+            tf.transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .rated_voltage(0)
+                .unwrap(),
+            380.0
+        );
+        assert!(tf.set_parameter("u3", 10.0).is_err());
+        assert!(tf3.set_parameter("u3", 10.0).is_ok());
+        assert_eq!(
+            tf3.transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .windings(),
+            3
+        );
+        assert!(matches!(
+            vt.transformer_parameters(),
+            Err(Error::NoTransformerParameters(_))
+        ));
+    }
+
+    #[test]
+    fn component_infeeds() {
+        use crate::parameters::LoadModel;
+        let (cb, _, _, _, _) = create_test_components();
+        let load = Load::new("load");
+        let gen = Generator::new("gen");
+        let grid = ExternalGrid::new("grid");
+
+        assert_eq!(load.r#type(), ComponentType::Load);
+        assert_eq!(gen.r#type(), ComponentType::Generator);
+        assert_eq!(grid.r#type(), ComponentType::ExternalGrid);
+        assert!(load.terminal(0).is_ok());
+        assert!(gen.terminal(1).is_err());
+        assert!(grid.open().is_err());
+
+        load.set_parameter("p", 12.0).unwrap();
+        load.load_parameters()
+            .unwrap()
+            .write()
+            .unwrap()
+            .set_model(LoadModel::ConstantImpedance);
+        assert_eq!(
+            load.load_parameters().unwrap().read().unwrap().demand(1.0),
+            (12.0, 0.0)
+        );
+        assert_eq!(gen.parameter("v").unwrap(), 1.0);
+        assert_eq!(gen.parameters().len(), 4);
+        grid.set_parameter("sk", 5000.0).unwrap();
+        assert_eq!(
+            grid.external_grid_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .sk(),
+            5000.0
+        );
+        assert!(grid.set_parameter("p", 1.0).is_err());
+
+        assert!(matches!(
+            cb.load_parameters(),
+            Err(Error::NoLoadParameters(_))
+        ));
+        assert!(load.generator_parameters().is_err());
+        assert!(gen.external_grid_parameters().is_err());
+    }
+
     #[test]
     fn component_update() {
         let (cb, ds, es, vt, tf) = create_test_components();
 
-        assert!(cb.update(0f64).is_err());
+        assert!(matches!(cb.update(0f64), Err(Error::NoMeasurement(_))));
         assert!(ds.update(0f64).is_err());
         assert!(es.update(0f64).is_err());
         assert!(tf.update(0f64).is_err());
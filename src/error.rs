@@ -0,0 +1,239 @@
+use std::fmt;
+use std::io;
+
+use super::interlock::Operation;
+use super::json::JsonError;
+use super::position::SwitchPosition;
+use super::tag::Tag;
+
+/// Error returned by all fallible APIs in the crate
+#[derive(Debug)]
+pub enum Error {
+    /// A node or component with the given name already exists in the network
+    NameInUse(String),
+    /// No element of the given kind (node, component, source, ...) with the given name exists
+    NotFound {
+        /// Kind of element that was looked up
+        kind: &'static str,
+        /// Name that was looked up
+        name: String,
+    },
+    /// The component is already connected to the node
+    AlreadyConnected {
+        /// Component name
+        component: String,
+        /// Node name
+        node: String,
+    },
+    /// The component is not connected to the node
+    NotConnected {
+        /// Component name
+        component: String,
+        /// Node name
+        node: String,
+    },
+    /// The node or component with the given name is still connected and cannot be removed
+    StillConnected(String),
+    /// The terminal is already connected to a node
+    TerminalConnected,
+    /// The terminal is not connected to a node
+    TerminalNotConnected,
+    /// The component does not have a terminal with the given index
+    InvalidTerminal {
+        /// Component name
+        component: String,
+        /// Requested terminal index
+        index: usize,
+        /// Number of terminals of the component
+        count: usize,
+    },
+    /// The component with the given name has no switchgear position
+    NoPosition(String),
+    /// The component with the given name has no measurement
+    NoMeasurement(String),
+    /// The component with the given name is not a line or cable
+    NoLineParameters(String),
+    /// The component with the given name is not a transformer
+    NoTransformerParameters(String),
+    /// The component with the given name is not a load
+    NoLoadParameters(String),
+    /// The component with the given name is not a generator
+    NoGeneratorParameters(String),
+    /// The component with the given name is not an external grid
+    NoExternalGridParameters(String),
+    /// The switchgear is already open
+    AlreadyOpen,
+    /// The switchgear is already closed
+    AlreadyClosed,
+    /// The switchgear can not go from one position to the other
+    InvalidTransition {
+        /// Current position
+        from: SwitchPosition,
+        /// Requested position
+        to: SwitchPosition,
+    },
+    /// The tap changer is already at its highest or lowest position
+    TapLimit(i32),
+    /// The switchgear can not be operated while a safety tag is placed on it
+    Tagged {
+        /// Switchgear name
+        component: String,
+        /// The blocking tag
+        tag: Tag,
+    },
+    /// The switchgear operation is blocked by an interlocking rule
+    InterlockViolation {
+        /// Switchgear name
+        component: String,
+        /// Blocked operation
+        operation: Operation,
+        /// Description of the violated rule
+        rule: String,
+    },
+    /// Closing the switchgear would connect an energized node to earth
+    EarthFault {
+        /// Switchgear name
+        component: String,
+        /// Name of the node that would be shorted to earth
+        node: String,
+    },
+    /// The voltage transformer with the given name measures voltage or is energized
+    VoltagePresent(String),
+    /// The node with the given name is already a source
+    AlreadySource(String),
+    /// There is no change to undo
+    NothingToUndo,
+    /// There is no undone change to redo
+    NothingToRedo,
+    /// An argument could not be interpreted
+    InvalidArgument(String),
+    /// A json document could not be parsed
+    JsonSyntax(JsonError),
+    /// A json document was parsed but does not describe a valid object
+    InvalidJson(String),
+    /// An I/O operation failed
+    Io {
+        /// What was being done when the error occurred
+        context: String,
+        /// The underlying I/O error
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NameInUse(name) => write!(
+                f,
+                "A node or component with name {} already exists in the network",
+                name
+            ),
+            Error::NotFound { kind, name } => write!(f, "No {} with name {} exists", kind, name),
+            Error::AlreadyConnected { component, node } => write!(
+                f,
+                "Component {} is already connected to node {}",
+                component, node
+            ),
+            Error::NotConnected { component, node } => write!(
+                f,
+                "Component {} is not connected to node {}",
+                component, node
+            ),
+            Error::StillConnected(name) => write!(f, "{} is still connected", name),
+            Error::TerminalConnected => write!(f, "Terminal already connected"),
+            Error::TerminalNotConnected => write!(f, "Terminal not connected"),
+            Error::InvalidTerminal {
+                component,
+                index,
+                count,
+            } => write!(
+                f,
+                "Component {} does not have a terminal with index {}; it only has {} terminals",
+                component, index, count
+            ),
+            Error::NoPosition(name) => write!(f, "Component {} has no position", name),
+            Error::NoMeasurement(name) => write!(f, "Component {} has no measurement", name),
+            Error::NoLineParameters(name) => write!(f, "Component {} has no line parameters", name),
+            Error::NoTransformerParameters(name) => {
+                write!(f, "Component {} has no transformer parameters", name)
+            }
+            Error::NoLoadParameters(name) => write!(f, "Component {} has no load parameters", name),
+            Error::NoGeneratorParameters(name) => {
+                write!(f, "Component {} has no generator parameters", name)
+            }
+            Error::NoExternalGridParameters(name) => {
+                write!(f, "Component {} has no external grid parameters", name)
+            }
+            Error::AlreadyOpen => write!(f, "Switchgear already open"),
+            Error::AlreadyClosed => write!(f, "Switchgear already closed"),
+            Error::InvalidTransition { from, to } => {
+                write!(f, "Switchgear can not go from {} to {}", from, to)
+            }
+            Error::TapLimit(position) => {
+                write!(f, "Tap changer already at limit position {}", position)
+            }
+            Error::Tagged { component, tag } => {
+                write!(f, "Cannot operate {}; tagged {}", component, tag)
+            }
+            Error::InterlockViolation {
+                component,
+                operation,
+                rule,
+            } => write!(f, "Cannot {} {}; interlock: {}", operation, component, rule),
+            Error::EarthFault { component, node } => write!(
+                f,
+                "Closing {} would short node {} to earth",
+                component, node
+            ),
+            Error::VoltagePresent(name) => write!(f, "Voltage present on {}", name),
+            Error::AlreadySource(name) => write!(f, "Node {} is already a source", name),
+            Error::NothingToUndo => write!(f, "Nothing to undo"),
+            Error::NothingToRedo => write!(f, "Nothing to redo"),
+            Error::InvalidArgument(message) => write!(f, "{}", message),
+            Error::JsonSyntax(e) => write!(f, "Invalid json: {}", e),
+            Error::InvalidJson(message) => write!(f, "Invalid json: {}", message),
+            Error::Io { context, source } => write!(f, "{} - {}", context, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::JsonSyntax(e) => Some(e),
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<JsonError> for Error {
+    fn from(e: JsonError) -> Error {
+        Error::JsonSyntax(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_display() {
+        let e = Error::NotFound {
+            kind: "node",
+            name: "n".to_string(),
+        };
+        assert_eq!(e.to_string(), "No node with name n exists");
+        assert_eq!(
+            Error::AlreadyClosed.to_string(),
+            "Switchgear already closed"
+        );
+
+        let e = Error::Io {
+            context: "Failed to open file".to_string(),
+            source: io::Error::new(io::ErrorKind::NotFound, "missing"),
+        };
+        assert_eq!(e.to_string(), "Failed to open file - missing");
+        assert!(std::error::Error::source(&e).is_some());
+    }
+}
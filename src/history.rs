@@ -0,0 +1,267 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::busbar::Busbar;
+use super::component::Component;
+use super::interlock::Interlock;
+use super::network::{ComponentId, NodeId};
+use super::node::Node;
+use super::parameters::{LoadModel, VectorGroup};
+use super::position::SwitchPosition;
+use super::simulation::InFlight;
+use super::tag::Tag;
+
+/// Reversible edit of a [Network](super::network::Network), recorded for undo and redo
+#[derive(Clone)]
+pub(crate) enum Edit {
+    /// Put a node in its arena slot
+    InsertNode { id: NodeId, node: Arc<Node> },
+    /// Empty the arena slot of a disconnected node
+    DeleteNode { id: NodeId, node: Arc<Node> },
+    /// Put a component in its arena slot
+    InsertComponent {
+        id: ComponentId,
+        component: Arc<dyn Component>,
+    },
+    /// Empty the arena slot of a disconnected component
+    DeleteComponent {
+        id: ComponentId,
+        component: Arc<dyn Component>,
+    },
+    /// Connect a component terminal to a node
+    Connect {
+        node: NodeId,
+        component: ComponentId,
+        terminal: usize,
+    },
+    /// Disconnect a component terminal from a node
+    Disconnect {
+        node: NodeId,
+        component: ComponentId,
+        terminal: usize,
+    },
+    /// Open switchgear
+    Open(ComponentId),
+    /// Close switchgear
+    Close(ComponentId),
+    /// Set a switchgear position reported by telemetry
+    Position {
+        component: ComponentId,
+        from: SwitchPosition,
+        to: SwitchPosition,
+    },
+    /// Start a switchgear operation; the switchgear travels until the operation finishes
+    Start(InFlight),
+    /// Finish a switchgear operation; completed, or cancelled back to the position it started from
+    Finish { operation: InFlight, complete: bool },
+    /// Move the simulation clock
+    Clock { from: Duration, to: Duration },
+    /// Change the operating time of switchgear
+    OperatingTime {
+        component: ComponentId,
+        from: Duration,
+        to: Duration,
+    },
+    /// Place a safety tag on a component
+    AddTag { component: ComponentId, tag: Tag },
+    /// Remove a safety tag from a component
+    RemoveTag { component: ComponentId, tag: Tag },
+    /// Change an electrical parameter of a component
+    Parameter {
+        component: ComponentId,
+        name: &'static str,
+        from: f64,
+        to: f64,
+    },
+    /// Change the tap position of a transformer
+    Tap {
+        component: ComponentId,
+        from: i32,
+        to: i32,
+    },
+    /// Change the vector group of a transformer
+    VectorGroup {
+        component: ComponentId,
+        from: VectorGroup,
+        to: VectorGroup,
+    },
+    /// Change the model of a load
+    LoadModel {
+        component: ComponentId,
+        from: LoadModel,
+        to: LoadModel,
+    },
+    /// Change a measurement value
+    Update {
+        component: ComponentId,
+        from: f64,
+        to: f64,
+    },
+    /// Designate a node as source
+    AddSource(NodeId),
+    /// Remove the source designation of a node
+    RemoveSource(NodeId),
+    /// Designate nodes as the sections of a busbar
+    AddBusbar(Busbar),
+    /// Remove a busbar designation
+    RemoveBusbar(Busbar),
+    /// Add an interlocking rule
+    AddInterlock(Interlock),
+    /// Remove an interlocking rule
+    RemoveInterlock(Interlock),
+    /// Edits that are done and undone together, in order; if one fails, the ones before it are rolled back
+    Batch(Vec<Edit>),
+}
+
+impl Edit {
+    /// Return the edit that reverts this one
+    pub(crate) fn inverse(&self) -> Edit {
+        match self.clone() {
+            Edit::InsertNode { id, node } => Edit::DeleteNode { id, node },
+            Edit::DeleteNode { id, node } => Edit::InsertNode { id, node },
+            Edit::InsertComponent { id, component } => Edit::DeleteComponent { id, component },
+            Edit::DeleteComponent { id, component } => Edit::InsertComponent { id, component },
+            Edit::Connect {
+                node,
+                component,
+                terminal,
+            } => Edit::Disconnect {
+                node,
+                component,
+                terminal,
+            },
+            Edit::Disconnect {
+                node,
+                component,
+                terminal,
+            } => Edit::Connect {
+                node,
+                component,
+                terminal,
+            },
+            Edit::Open(id) => Edit::Close(id),
+            Edit::Close(id) => Edit::Open(id),
+            Edit::Position {
+                component,
+                from,
+                to,
+            } => Edit::Position {
+                component,
+                from: to,
+                to: from,
+            },
+            Edit::Start(operation) => Edit::Finish {
+                operation,
+                complete: false,
+            },
+            Edit::Finish { operation, .. } => Edit::Start(operation),
+            Edit::Clock { from, to } => Edit::Clock { from: to, to: from },
+            Edit::OperatingTime {
+                component,
+                from,
+                to,
+            } => Edit::OperatingTime {
+                component,
+                from: to,
+                to: from,
+            },
+            Edit::AddTag { component, tag } => Edit::RemoveTag { component, tag },
+            Edit::RemoveTag { component, tag } => Edit::AddTag { component, tag },
+            Edit::Parameter {
+                component,
+                name,
+                from,
+                to,
+            } => Edit::Parameter {
+                component,
+                name,
+                from: to,
+                to: from,
+            },
+            Edit::Tap {
+                component,
+                from,
+                to,
+            } => Edit::Tap {
+                component,
+                from: to,
+                to: from,
+            },
+            Edit::VectorGroup {
+                component,
+                from,
+                to,
+            } => Edit::VectorGroup {
+                component,
+                from: to,
+                to: from,
+            },
+            Edit::LoadModel {
+                component,
+                from,
+                to,
+            } => Edit::LoadModel {
+                component,
+                from: to,
+                to: from,
+            },
+            Edit::Update {
+                component,
+                from,
+                to,
+            } => Edit::Update {
+                component,
+                from: to,
+                to: from,
+            },
+            Edit::AddSource(id) => Edit::RemoveSource(id),
+            Edit::RemoveSource(id) => Edit::AddSource(id),
+            Edit::AddBusbar(busbar) => Edit::RemoveBusbar(busbar),
+            Edit::RemoveBusbar(busbar) => Edit::AddBusbar(busbar),
+            Edit::AddInterlock(rule) => Edit::RemoveInterlock(rule),
+            Edit::RemoveInterlock(rule) => Edit::AddInterlock(rule),
+            Edit::Batch(edits) => Edit::Batch(edits.iter().rev().map(Edit::inverse).collect()),
+        }
+    }
+}
+
+/// Undo and redo stacks of [Edit]
+#[derive(Default)]
+pub(crate) struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl History {
+    /// Record a new edit; it can be undone, and anything undone before can no longer be redone
+    pub(crate) fn record(&mut self, edit: Edit) {
+        self.undo.push(edit);
+        self.redo.clear();
+    }
+
+    /// Take the last edit to undo
+    pub(crate) fn pop_undo(&mut self) -> Option<Edit> {
+        self.undo.pop()
+    }
+
+    /// Take the last undone edit to redo
+    pub(crate) fn pop_redo(&mut self) -> Option<Edit> {
+        self.redo.pop()
+    }
+
+    /// Store an edit that was undone, so it can be redone
+    pub(crate) fn push_redo(&mut self, edit: Edit) {
+        self.redo.push(edit);
+    }
+
+    /// Store an edit that was redone, so it can be undone again
+    pub(crate) fn push_undo(&mut self, edit: Edit) {
+        self.undo.push(edit);
+    }
+
+    /// Forget all edits
+    pub(crate) fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+
+use super::analysis::EnergizationState;
+use super::error::Error;
+use super::network::{Graph, Network};
+
+/// Switchgear operation checked by an [Interlock]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operation {
+    /// Open the switchgear
+    Open,
+    /// Close the switchgear
+    Close,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opstr = match self {
+            Operation::Open => "open",
+            Operation::Close => "close",
+        };
+        write!(f, "{}", opstr)
+    }
+}
+
+/// Interlocking rule guarding the operation of a switch, see [Network::add_interlock]
+#[derive(Debug, PartialEq, Clone)]
+pub enum Interlock {
+    /// The switch may only open or close while the circuit breaker is open; keeps a disconnector from operating under load
+    BreakerOpen {
+        /// Name of the guarded switch
+        switch: String,
+        /// Name of the circuit breaker that has to be open
+        breaker: String,
+    },
+    /// The switch may only close while none of its nodes are energized; keeps an earthing switch from closing onto a live node
+    DeadNodes {
+        /// Name of the guarded switch
+        switch: String,
+    },
+}
+
+impl Interlock {
+    /// Return the name of the switch guarded by this rule
+    pub fn switch(&self) -> &String {
+        match self {
+            Interlock::BreakerOpen { switch, .. } => switch,
+            Interlock::DeadNodes { switch } => switch,
+        }
+    }
+
+    /// True if the rule refers to the component with the given name
+    pub fn references(&self, name: &str) -> bool {
+        match self {
+            Interlock::BreakerOpen { switch, breaker } => switch == name || breaker == name,
+            Interlock::DeadNodes { switch } => switch == name,
+        }
+    }
+
+    /// Check whether the given operation of the guarded switch is allowed in the current state of the network.
+    /// Returns [Error::InterlockViolation] describing the rule if it is not.
+    pub fn check(&self, net: &Network, operation: Operation) -> Result<(), Error> {
+        self.check_graph(&net.graph(), operation)
+    }
+
+    /// Check the rule against a locked graph, see [Interlock::check]
+    pub(crate) fn check_graph(&self, g: &Graph, operation: Operation) -> Result<(), Error> {
+        let allowed = match self {
+            Interlock::BreakerOpen { breaker, .. } => g
+                .component(g.component_id(breaker)?)?
+                .position()?
+                .read()
+                .unwrap()
+                .is_open(),
+            Interlock::DeadNodes { switch } => {
+                operation == Operation::Open || {
+                    let e = g.energization();
+                    g.component(g.component_id(switch)?)?
+                        .nodes()
+                        .iter()
+                        .filter_map(|n| g.node(*n).ok())
+                        .all(|n| e.node_state(n.name()).ok() != Some(EnergizationState::Energized))
+                }
+            }
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(Error::InterlockViolation {
+                component: self.switch().clone(),
+                operation,
+                rule: self.to_string(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for Interlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Interlock::BreakerOpen { switch, breaker } => {
+                write!(f, "{} may only operate while {} is open", switch, breaker)
+            }
+            Interlock::DeadNodes { switch } => {
+                write!(f, "{} may only close onto de-energized nodes", switch)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::*;
+    use crate::network::RemoveMode;
+
+    /// source - cb - node1 - ds - node2, with an earthing switch on node1
+    fn create_test_network() -> Network {
+        let net = Network::new("net");
+        for n in ["source", "node1", "node2"] {
+            net.create_node(n).unwrap();
+        }
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.create_component::<EarthingSwitch>("es").unwrap();
+        net.connect("source", "cb", 0).unwrap();
+        net.connect("node1", "cb", 1).unwrap();
+        net.connect("node1", "ds", 0).unwrap();
+        net.connect("node2", "ds", 1).unwrap();
+        net.connect("node1", "es", 0).unwrap();
+        net.add_source("source").unwrap();
+        net.add_interlock(Interlock::BreakerOpen {
+            switch: "ds".to_string(),
+            breaker: "cb".to_string(),
+        })
+        .unwrap();
+        net.add_interlock(Interlock::DeadNodes {
+            switch: "es".to_string(),
+        })
+        .unwrap();
+        net
+    }
+
+    #[test]
+    fn interlock_breaker_open() {
+        let net = create_test_network();
+        net.close("cb").unwrap();
+        assert!(matches!(
+            net.close("ds"),
+            Err(Error::InterlockViolation {
+                operation: Operation::Close,
+                ..
+            })
+        ));
+        assert!(net
+            .get_component("ds")
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_open());
+
+        net.open("cb").unwrap();
+        net.close("ds").unwrap();
+        net.close("cb").unwrap();
+        assert!(net.open("ds").is_err());
+    }
+
+    #[test]
+    fn interlock_dead_nodes() {
+        let net = create_test_network();
+        net.close("cb").unwrap();
+        let e = net.close("es").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "Cannot close es; interlock: es may only close onto de-energized nodes"
+        );
+
+        net.open("cb").unwrap();
+        net.close("es").unwrap();
+        net.open("es").unwrap();
+    }
+
+    #[test]
+    fn interlock_rules() {
+        let net = create_test_network();
+        assert_eq!(net.interlocks("ds").len(), 1);
+        assert!(net.interlocks("node1").is_empty());
+        assert!(net
+            .add_interlock(Interlock::DeadNodes {
+                switch: "es".to_string()
+            })
+            .is_err());
+        assert!(net
+            .add_interlock(Interlock::DeadNodes {
+                switch: "node1".to_string()
+            })
+            .is_err());
+
+        net.remove_component("cb", RemoveMode::Cascade).unwrap();
+        assert!(net.interlocks("ds").is_empty());
+        net.remove_interlock(&Interlock::DeadNodes {
+            switch: "es".to_string(),
+        })
+        .unwrap();
+        assert!(net.interlocks("es").is_empty());
+        assert!(net
+            .remove_interlock(&Interlock::DeadNodes {
+                switch: "es".to_string()
+            })
+            .is_err());
+    }
+}
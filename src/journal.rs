@@ -0,0 +1,316 @@
+use std::fmt;
+use std::fs::{create_dir_all, File};
+use std::io::prelude::*;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::error::Error;
+
+/// Origin of a change to a [Network](super::network::Network), see [Network::set_event_source](super::network::Network::set_event_source)
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum EventSource {
+    /// The interactive command line interface
+    Cli,
+    /// The Rust API
+    #[default]
+    Api,
+    /// A protocol adapter with the given name
+    Protocol(String),
+}
+
+impl fmt::Display for EventSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EventSource::Cli => write!(f, "CLI"),
+            EventSource::Api => write!(f, "API"),
+            EventSource::Protocol(name) => write!(f, "Protocol {}", name),
+        }
+    }
+}
+
+/// Kind of change recorded by an [Event]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EventKind {
+    /// A switchgear opened or closed, or a tap changer changed position
+    Position,
+    /// A measurement value changed
+    Measurement,
+    /// A node or component was created, removed, connected or disconnected
+    Topology,
+    /// A setting changed: a source designation, busbar, interlocking rule or safety tag was added or removed,
+    /// or an electrical parameter, operating time, vector group or load model was set
+    Configuration,
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kindstr = match self {
+            EventKind::Position => "Position",
+            EventKind::Measurement => "Measurement",
+            EventKind::Topology => "Topology",
+            EventKind::Configuration => "Configuration",
+        };
+        write!(f, "{}", kindstr)
+    }
+}
+
+/// A single state change of a network
+#[derive(Debug, PartialEq, Clone)]
+pub struct Event {
+    time: SystemTime,
+    sim_time: Duration,
+    source: EventSource,
+    kind: EventKind,
+    name: String,
+    description: String,
+}
+
+impl Event {
+    /// Constructor; timestamps the event with the current time and the given simulation time
+    pub fn new(
+        sim_time: Duration,
+        source: EventSource,
+        kind: EventKind,
+        name: &str,
+        description: &str,
+    ) -> Event {
+        Event {
+            time: SystemTime::now(),
+            sim_time,
+            source,
+            kind,
+            name: name.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    /// Return the time of the change
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Return the time of the change in seconds since the unix epoch
+    pub fn timestamp(&self) -> f64 {
+        match self.time.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs_f64(),
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Return the simulation time of the change, see [Network::advance](super::network::Network::advance)
+    pub fn sim_time(&self) -> Duration {
+        self.sim_time
+    }
+
+    /// Return the origin of the change
+    pub fn source(&self) -> &EventSource {
+        &self.source
+    }
+
+    /// Return the kind of change
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    /// Return the name of the changed component or node
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Return a description of the change
+    pub fn description(&self) -> &String {
+        &self.description
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.3} (sim {:.3} s) [{}] {} {}: {}",
+            self.timestamp(),
+            self.sim_time.as_secs_f64(),
+            self.source,
+            self.kind,
+            self.name,
+            self.description
+        )
+    }
+}
+
+/// Append-only log of the [Event] of a network, in the order they happened
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Journal {
+    events: Vec<Event>,
+}
+
+impl Journal {
+    /// Constructor
+    pub fn new() -> Journal {
+        Journal { events: vec![] }
+    }
+
+    /// Append an event
+    pub fn record(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Drop the events after the first `len`, taking back the events of a change that was rolled back
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.events.truncate(len);
+    }
+
+    /// Return all events
+    pub fn events(&self) -> &Vec<Event> {
+        &self.events
+    }
+
+    /// Return the events of the component or node with the given name
+    pub fn for_name(&self, name: &str) -> Journal {
+        self.filter(|e| e.name() == name)
+    }
+
+    /// Return the events that happened within the given time range, including both ends
+    pub fn between(&self, from: SystemTime, to: SystemTime) -> Journal {
+        self.filter(|e| e.time() >= from && e.time() <= to)
+    }
+
+    fn filter<F: Fn(&Event) -> bool>(&self, f: F) -> Journal {
+        Journal {
+            events: self.events.iter().filter(|e| f(e)).cloned().collect(),
+        }
+    }
+
+    /// Write the events as csv with a header line to a stream
+    pub fn write_csv<W: Write>(&self, mut w: W) -> Result<(), Error> {
+        let mut csv = String::from("time,sim_time,source,kind,name,description\n");
+        for e in &self.events {
+            csv += &format!(
+                "{:.3},{:.3},{},{},{},{}\n",
+                e.timestamp(),
+                e.sim_time().as_secs_f64(),
+                csv_field(&e.source().to_string()),
+                e.kind(),
+                csv_field(e.name()),
+                csv_field(e.description())
+            );
+        }
+        match w.write_all(csv.as_bytes()).and_then(|_| w.flush()) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(Error::Io {
+                context: "Failed to write csv".to_string(),
+                source: e,
+            }),
+        }
+    }
+
+    /// Write the events as csv to a file at the given path, creating missing parent directories
+    pub fn to_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if let Err(e) = create_dir_all(parent) {
+                return Err(Error::Io {
+                    context: format!("Failed to create directory {}", parent.display()),
+                    source: e,
+                });
+            }
+        }
+        match File::create(path) {
+            Ok(file) => self.write_csv(file),
+            Err(e) => Err(Error::Io {
+                context: format!("Failed to create csv file {}", path.display()),
+                source: e,
+            }),
+        }
+    }
+}
+
+/// Quote a csv field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::*;
+    use crate::json::JsonWritable;
+    use crate::network::Network;
+    use std::time::Duration;
+
+    #[test]
+    fn journal_network() {
+        let net = Network::new("net");
+        let start = SystemTime::now();
+        net.create_node("node").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.connect("node", "cb", 0).unwrap();
+        net.set_event_source(EventSource::Protocol("iec61850".to_string()));
+        net.close("cb").unwrap();
+        net.undo().unwrap();
+
+        let j = net.journal();
+        assert_eq!(j.events().len(), 5);
+        assert_eq!(j.events()[0].kind(), EventKind::Topology);
+        assert_eq!(j.events()[0].source(), &EventSource::Api);
+        assert_eq!(j.events()[0].name(), "node");
+
+        let cb = j.for_name("cb");
+        assert_eq!(cb.events().len(), 4);
+        assert_eq!(
+            cb.events()[1].description(),
+            "Connected terminal 0 to node node"
+        );
+        assert_eq!(cb.events()[2].kind(), EventKind::Position);
+        assert_eq!(cb.events()[2].description(), "Closed");
+        assert_eq!(cb.events()[3].description(), "Opened");
+        assert_eq!(
+            cb.events()[3].source(),
+            &EventSource::Protocol("iec61850".to_string())
+        );
+
+        assert_eq!(j.between(start, SystemTime::now()).events().len(), 5);
+        assert!(j
+            .between(
+                start - Duration::from_secs(10),
+                start - Duration::from_secs(5)
+            )
+            .events()
+            .is_empty());
+    }
+
+    #[test]
+    fn journal_export() {
+        let mut j = Journal::new();
+        j.record(Event::new(
+            Duration::from_millis(1500),
+            EventSource::Cli,
+            EventKind::Measurement,
+            "vt, \"main\"",
+            "Updated 0 to 10",
+        ));
+
+        let mut buf = vec![];
+        j.write_csv(&mut buf).unwrap();
+        let csv = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "time,sim_time,source,kind,name,description");
+        assert!(lines[1].ends_with(",1.500,CLI,Measurement,\"vt, \"\"main\"\"\",Updated 0 to 10"));
+
+        let mut buf = vec![];
+        j.write_json(&mut buf).unwrap();
+        let json = crate::json::JsonValue::parse(&String::from_utf8(buf).unwrap()).unwrap();
+        let event = &json.get("events").unwrap().as_array().unwrap()[0];
+        assert_eq!(event.get("name").unwrap().as_str().unwrap(), "vt, \"main\"");
+        assert_eq!(event.get("kind").unwrap().as_str().unwrap(), "Measurement");
+        assert_eq!(
+            event.get("time").unwrap().as_f64().unwrap(),
+            j.events()[0].timestamp()
+        );
+        assert_eq!(event.get("sim_time").unwrap().as_f64().unwrap(), 1.5);
+    }
+}
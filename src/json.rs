@@ -1,165 +1,1382 @@
-use std::fs::{OpenOptions, create_dir, read_to_string};
+use std::fmt;
+use std::fs::{create_dir_all, File};
 use std::io::prelude::*;
-use std::io::{ErrorKind, Error};
-use std::collections::HashMap;
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+use std::sync::Arc;
+use std::time::Duration;
 
-use super::network::Network;
 use super::component::*;
+use super::error::Error;
+use super::interlock::Interlock;
+use super::journal::Journal;
+use super::network::Network;
 use super::node::Node;
-
-/// Strip the first and last character of a string
-fn strip_outer_characters(s: &str) -> String {
-    let mut chars = s.chars();
-    chars.next();
-    chars.next_back();
-    chars.as_str().to_string()
-}
+use super::parameters::{LoadModel, VectorGroup};
+use super::position::SwitchPosition;
+use super::tag::{Tag, TagKind};
 
 /// Trait to (de)serialize objects to/from json
-pub trait JsonSerializable: JsonReadable + JsonWritable {
-}
+pub trait JsonSerializable: JsonReadable + JsonWritable {}
 
+/// Trait to read objects from json
 pub trait JsonReadable {
-    /// Get object intance from json representation
-    fn from_json(filename: &str) -> Result<Box<Self>, String>;
+    /// Get object instance from a json stream
+    fn read_json<R: Read>(reader: R) -> Result<Box<Self>, Error>;
+
+    /// Get object instance from the json file at the given path
+    fn from_json<P: AsRef<Path>>(path: P) -> Result<Box<Self>, Error> {
+        let path = path.as_ref();
+        match File::open(path) {
+            Ok(file) => Self::read_json(file),
+            Err(e) => Err(Error::Io {
+                context: format!("Failed to open json file {}", path.display()),
+                source: e,
+            }),
+        }
+    }
 }
 
+/// Trait to write objects to json
 pub trait JsonWritable {
-    /// Get json representation of object
-    fn to_json(&self) -> Result<(), String>;
+    /// Write json representation of object to a stream
+    fn write_json<W: Write>(&self, writer: W) -> Result<(), Error>;
+
+    /// Write json representation of object to a file at the given path, creating missing parent directories
+    fn to_json<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if let Err(e) = create_dir_all(parent) {
+                return Err(Error::Io {
+                    context: format!("Failed to create directory {}", parent.display()),
+                    source: e,
+                });
+            }
+        }
+        match File::create(path) {
+            Ok(file) => self.write_json(file),
+            Err(e) => Err(Error::Io {
+                context: format!("Failed to create json file {}", path.display()),
+                source: e,
+            }),
+        }
+    }
 }
 
-struct JsonReader<'a> {
-    filename: &'a str,
-    keys: HashMap<String, String>,
+/// A json value
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonValue {
+    /// null
+    Null,
+    /// true or false
+    Bool(bool),
+    /// A number; written as null if it is not finite
+    Number(f64),
+    /// A string
+    String(String),
+    /// An array of values
+    Array(Vec<JsonValue>),
+    /// An object; the key-value pairs are kept in order
+    Object(Vec<(String, JsonValue)>),
 }
 
-impl JsonReader<'_> {
-    fn new(filename: &str) -> JsonReader {
-        JsonReader {
-            filename,
-            keys: HashMap::new(),
+impl JsonValue {
+    /// Parse a json document; arrays and objects may be nested up to 128 levels deep
+    pub fn parse(json: &str) -> Result<JsonValue, JsonError> {
+        let mut parser = JsonParser::new(json);
+        parser.skip_whitespace();
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(value),
+            Some(c) => Err(parser.error(format!("Unexpected character '{}' after json value", c))),
         }
     }
 
-    fn get_key(&self, key: &str) -> Result<&str, String> {
-        match self.keys.get(key) {
-            Some(s) => Ok(s),
-            None => Err(format!("Invalid Json: Key {} not found in json file {}", key, self.filename)),
-        }
-    }
-
-    fn read(&mut self) -> Result<(), String> {
-        let mut json: String;
-        match read_to_string(format!("{}/{}.json", "json", self.filename)) {
-            Err(e) => panic!("{}", e),
-            Ok(s) => {
-                json = s;
-                json.retain(|c| !c.is_whitespace());
-                json = strip_outer_characters(json.as_str()); // Strip braces
-                let split = json.split(',');
-                for item in split.clone() {
-                    let split2 = item.split(':').collect::<Vec<&str>>();
-                    let key = split2.get(0);
-                    let value = split2.get(1);
-                    match (key, value) {
-                        (Some(k), Some(v)) => {
-                            let k = strip_outer_characters(k); // Strip quotes
-                            let v = strip_outer_characters(v); // Strip quotes
-                            self.keys.insert(k, v);
-                        },
-                        (_, _) => return Err(format!("Failed to read json file {} on k-v pair {}", self.filename, item)),
-                    }
+    /// Get the value of the given key if this is an object that contains the key
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(pairs) => pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Get the string if this is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Get the number if this is a number
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Get the values if this is an array
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Write the value with the given indentation level, four spaces per level
+    fn write(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            JsonValue::Number(n) if n.is_finite() => write!(f, "{}", n),
+            JsonValue::Number(_) => write!(f, "null"),
+            JsonValue::String(s) => write_string(f, s),
+            JsonValue::Array(values) if values.is_empty() => write!(f, "[]"),
+            JsonValue::Array(values) => {
+                writeln!(f, "[")?;
+                for (i, v) in values.iter().enumerate() {
+                    write!(f, "{:1$}", "", (indent + 1) * 4)?;
+                    v.write(f, indent + 1)?;
+                    writeln!(f, "{}", if i + 1 < values.len() { "," } else { "" })?;
                 }
-                Ok(())
+                write!(f, "{:1$}]", "", indent * 4)
+            }
+            JsonValue::Object(pairs) if pairs.is_empty() => write!(f, "{{}}"),
+            JsonValue::Object(pairs) => {
+                writeln!(f, "{{")?;
+                for (i, (k, v)) in pairs.iter().enumerate() {
+                    write!(f, "{:1$}", "", (indent + 1) * 4)?;
+                    write_string(f, k)?;
+                    write!(f, ": ")?;
+                    v.write(f, indent + 1)?;
+                    writeln!(f, "{}", if i + 1 < pairs.len() { "," } else { "" })?;
+                }
+                write!(f, "{:1$}}}", "", indent * 4)
             }
         }
     }
 }
 
-struct JsonWriter<'a> {
-    filename: &'a str,
-    json: String,
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> JsonValue {
+        JsonValue::String(s.to_string())
+    }
 }
 
-impl JsonWriter<'_> {
-    fn new(filename: &str) -> JsonWriter {
-        match create_dir("json") {
-            Err(e) if e.kind() == ErrorKind::AlreadyExists => {},
-            Err(e) => panic!("{}", e),
-            Ok(_) => {},
+impl From<&String> for JsonValue {
+    fn from(s: &String) -> JsonValue {
+        JsonValue::String(s.clone())
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(n: f64) -> JsonValue {
+        JsonValue::Number(n)
+    }
+}
+
+/// Write a quoted and escaped json string
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            '\u{8}' => write!(f, "\\b")?,
+            '\u{c}' => write!(f, "\\f")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
         }
-        JsonWriter {
-            filename,
-            json: "{\n".to_string(),
+    }
+    write!(f, "\"")
+}
+
+/// Error while parsing json, with the line and column (both starting at 1) where it occurred
+#[derive(Debug, PartialEq)]
+pub struct JsonError {
+    message: String,
+    line: usize,
+    column: usize,
+}
+
+impl JsonError {
+    /// Return the error message
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+
+    /// Return the line where the error occurred
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the column where the error occurred
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Maximum nesting depth of json arrays and objects, which keeps deeply nested input from overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+/// Recursive descent json parser, see RFC 8259
+struct JsonParser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn new(json: &str) -> JsonParser<'_> {
+        JsonParser {
+            chars: json.chars().peekable(),
+            line: 1,
+            column: 1,
+            depth: 0,
         }
     }
 
-    fn add_key<T: ToString>(&mut self, key: &'static str, value: T) {
-        let s = format!("    \"{}\": \"{}\",\n", key, value.to_string());
-        self.json += s.as_str();
+    fn error(&self, message: String) -> JsonError {
+        JsonError {
+            message,
+            line: self.line,
+            column: self.column,
+        }
     }
 
-    fn write(&mut self) -> std::io::Result<()> {
-        let mut chars = self.json.chars();
-        chars.next_back();
-        chars.next_back();
-        self.json = chars.as_str().to_string() + "\n}";
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
 
-        let mut file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(format!("{}/{}.json", "json", self.filename))?;
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        match c {
+            Some('\n') => {
+                self.line += 1;
+                self.column = 1;
+            }
+            Some(_) => self.column += 1,
+            None => {}
+        }
+        c
+    }
 
-        file.write_all(self.json.as_bytes())?;
+    fn expect(&mut self, expected: char) -> Result<(), JsonError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.next();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("Expected '{}' but found '{}'", expected, c))),
+            None => Err(self.error(format!("Expected '{}' but found end of input", expected))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+            self.next();
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, JsonError> {
+        match self.peek() {
+            Some(c @ ('{' | '[')) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(format!("Nesting deeper than {} levels", MAX_DEPTH)));
+                }
+                self.depth += 1;
+                let value = match c {
+                    '{' => self.parse_object(),
+                    _ => self.parse_array(),
+                };
+                self.depth -= 1;
+                value
+            }
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some('t') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some('f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some('n') => self.parse_literal("null", JsonValue::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) => Err(self.error(format!("Unexpected character '{}'", c))),
+            None => Err(self.error("Unexpected end of input".to_string())),
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        for expected in literal.chars() {
+            match self.peek() {
+                Some(c) if c == expected => {
+                    self.next();
+                }
+                _ => return Err(self.error(format!("Invalid literal, expected {}", literal))),
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_digits(&mut self, number: &mut String) -> Result<(), JsonError> {
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => {}
+            _ => return Err(self.error("Invalid number, expected a digit".to_string())),
+        }
+        while let Some(c) = self.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            number.push(c);
+            self.next();
+        }
         Ok(())
     }
+
+    fn parse_number(&mut self) -> Result<JsonValue, JsonError> {
+        let (line, column) = (self.line, self.column);
+        let mut number = String::new();
+        if self.peek() == Some('-') {
+            number.push('-');
+            self.next();
+        }
+        if self.peek() == Some('0') {
+            number.push('0');
+            self.next();
+        } else {
+            self.parse_digits(&mut number)?;
+        }
+        if self.peek() == Some('.') {
+            number.push('.');
+            self.next();
+            self.parse_digits(&mut number)?;
+        }
+        if let Some('e') | Some('E') = self.peek() {
+            number.push('e');
+            self.next();
+            if let Some(c @ '+') | Some(c @ '-') = self.peek() {
+                number.push(c);
+                self.next();
+            }
+            self.parse_digits(&mut number)?;
+        }
+        match number.parse::<f64>() {
+            Ok(n) => Ok(JsonValue::Number(n)),
+            Err(_) => Err(JsonError {
+                message: format!("Invalid number {}", number),
+                line,
+                column,
+            }),
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonError> {
+        let mut value = 0;
+        for _ in 0..4 {
+            match self.peek().and_then(|c| c.to_digit(16)) {
+                Some(d) => {
+                    value = value * 16 + d;
+                    self.next();
+                }
+                None => return Err(self.error("Invalid unicode escape".to_string())),
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("Unterminated string".to_string())),
+                Some('"') => {
+                    self.next();
+                    return Ok(s);
+                }
+                Some('\\') => {
+                    self.next();
+                    let c = match self.peek() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            self.next();
+                            s.push(self.parse_unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("Invalid escape sequence".to_string())),
+                    };
+                    self.next();
+                    s.push(c);
+                }
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(self.error("Control character in string".to_string()))
+                }
+                Some(c) => {
+                    self.next();
+                    s.push(c);
+                }
+            }
+        }
+    }
+
+    /// Parse the hex digits of a \u escape, including a second escape for surrogate pairs
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if self.peek() != Some('\\') {
+                return Err(self.error("Unpaired surrogate in unicode escape".to_string()));
+            }
+            self.next();
+            self.expect('u')?;
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("Invalid surrogate pair in unicode escape".to_string()));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        match char::from_u32(code) {
+            Some(c) => Ok(c),
+            None => Err(self.error("Invalid unicode escape".to_string())),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect('[')?;
+        let mut values = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(JsonValue::Array(values));
+        }
+        loop {
+            self.skip_whitespace();
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                }
+                Some(']') => {
+                    self.next();
+                    return Ok(JsonValue::Array(values));
+                }
+                _ => return Err(self.error("Expected ',' or ']' in array".to_string())),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect('{')?;
+        let mut pairs = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(JsonValue::Object(pairs));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+            pairs.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                }
+                Some('}') => {
+                    self.next();
+                    return Ok(JsonValue::Object(pairs));
+                }
+                _ => return Err(self.error("Expected ',' or '}' in object".to_string())),
+            }
+        }
+    }
 }
 
-impl JsonWritable for dyn Component {
-    fn to_json(&self) -> Result<(), String> {
-        let mut writer = JsonWriter::new(self.name().as_str());
-        writer.add_key("name", self.name());
-        writer.add_key("type", format!("{}", self.r#type()));
-        match writer.write() {
+/// Reads a json stream into a [JsonValue] and gets typed values from it with descriptive errors
+struct JsonReader {
+    json: JsonValue,
+}
+
+impl JsonReader {
+    fn read<R: Read>(mut reader: R) -> Result<JsonReader, Error> {
+        let mut json = String::new();
+        if let Err(e) = reader.read_to_string(&mut json) {
+            return Err(Error::Io {
+                context: "Failed to read json".to_string(),
+                source: e,
+            });
+        }
+        Ok(JsonReader {
+            json: JsonValue::parse(&json)?,
+        })
+    }
+
+    fn get<'v>(&self, value: &'v JsonValue, key: &str) -> Result<&'v JsonValue, Error> {
+        match value.get(key) {
+            Some(v) => Ok(v),
+            None => Err(Error::InvalidJson(format!("Key {} not found", key))),
+        }
+    }
+
+    fn get_str<'v>(&self, value: &'v JsonValue, key: &str) -> Result<&'v str, Error> {
+        match self.get(value, key)?.as_str() {
+            Some(s) => Ok(s),
+            None => Err(Error::InvalidJson(format!("Key {} is not a string", key))),
+        }
+    }
+
+    fn get_f64(&self, value: &JsonValue, key: &str) -> Result<f64, Error> {
+        match self.get(value, key)?.as_f64() {
+            Some(v) => Ok(v),
+            None => Err(Error::InvalidJson(format!("Key {} is not a number", key))),
+        }
+    }
+
+    fn get_array<'v>(&self, value: &'v JsonValue, key: &str) -> Result<&'v Vec<JsonValue>, Error> {
+        match self.get(value, key)?.as_array() {
+            Some(a) => Ok(a),
+            None => Err(Error::InvalidJson(format!("Key {} is not an array", key))),
+        }
+    }
+
+    fn get_strings<'v>(&self, value: &'v JsonValue, key: &str) -> Result<Vec<&'v str>, Error> {
+        let mut strings = vec![];
+        for v in self.get_array(value, key)? {
+            match v.as_str() {
+                Some(s) => strings.push(s),
+                None => {
+                    return Err(Error::InvalidJson(format!(
+                        "Key {} contains a value that is not a string",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(strings)
+    }
+}
+
+/// Builds a json object and writes it to a stream
+struct JsonWriter {
+    json: Vec<(String, JsonValue)>,
+}
+
+impl JsonWriter {
+    fn new() -> JsonWriter {
+        JsonWriter { json: vec![] }
+    }
+
+    fn add_key<T: Into<JsonValue>>(&mut self, key: &str, value: T) {
+        self.json.push((key.to_string(), value.into()));
+    }
+
+    fn write<W: Write>(self, mut writer: W) -> Result<(), Error> {
+        let json = JsonValue::Object(self.json).to_string() + "\n";
+        match writer
+            .write_all(json.as_bytes())
+            .and_then(|_| writer.flush())
+        {
             Ok(_) => Ok(()),
-            Err(e) => Err(e.to_string())
+            Err(e) => Err(Error::Io {
+                context: "Failed to write json".to_string(),
+                source: e,
+            }),
         }
     }
 }
 
+impl JsonWritable for dyn Component {
+    fn write_json<W: Write>(&self, w: W) -> Result<(), Error> {
+        let mut writer = JsonWriter::new();
+        writer.add_key("name", self.name());
+        writer.add_key("type", format!("{:?}", self.r#type()).as_str());
+        writer.write(w)
+    }
+}
+
+impl JsonWritable for Journal {
+    fn write_json<W: Write>(&self, w: W) -> Result<(), Error> {
+        let mut writer = JsonWriter::new();
+        writer.add_key("type", "Journal");
+        let events = self
+            .events()
+            .iter()
+            .map(|e| {
+                JsonValue::Object(vec![
+                    ("time".to_string(), e.timestamp().into()),
+                    ("sim_time".to_string(), e.sim_time().as_secs_f64().into()),
+                    ("source".to_string(), e.source().to_string().as_str().into()),
+                    ("kind".to_string(), e.kind().to_string().as_str().into()),
+                    ("name".to_string(), e.name().into()),
+                    ("description".to_string(), e.description().into()),
+                ])
+            })
+            .collect();
+        writer.add_key("events", JsonValue::Array(events));
+        writer.write(w)
+    }
+}
+
 impl JsonReadable for CircuitBreaker {
-    fn from_json(filename: &str) -> Result<Box<CircuitBreaker>, String> {
-        let mut reader = JsonReader::new(filename);
-        reader.read()?;
-        let name = reader.get_key("name")?;
-        let r#type = reader.get_key("type")?;
+    fn read_json<R: Read>(r: R) -> Result<Box<CircuitBreaker>, Error> {
+        let reader = JsonReader::read(r)?;
+        let name = reader.get_str(&reader.json, "name")?;
+        let r#type = reader.get_str(&reader.json, "type")?;
         if r#type == "CircuitBreaker" {
             Ok(Box::new(CircuitBreaker::new(name)))
         } else {
-            Err(format!("Invalid json: component type CircuitBreaker expected in file {}", filename))
+            Err(Error::InvalidJson(
+                "component type CircuitBreaker expected".to_string(),
+            ))
         }
     }
 }
 
+/// Json array of the names of the given nodes
+fn node_names(nodes: &[Arc<Node>]) -> JsonValue {
+    JsonValue::Array(nodes.iter().map(|n| n.name().into()).collect())
+}
+
+impl JsonWritable for Network {
+    fn write_json<W: Write>(&self, w: W) -> Result<(), Error> {
+        let mut writer = JsonWriter::new();
+        writer.add_key("name", self.name());
+        writer.add_key("type", "Network");
+
+        writer.add_key("nodes", node_names(&self.nodes()));
+        writer.add_key("sources", node_names(&self.sources()));
+
+        let mut busbars = vec![];
+        for busbar in self.busbars() {
+            let mut sections = vec![];
+            for s in busbar.sections() {
+                sections.push(self.node(*s)?);
+            }
+            busbars.push(JsonValue::Object(vec![
+                ("name".to_string(), busbar.name().into()),
+                ("rated_voltage".to_string(), busbar.rated_voltage().into()),
+                ("sections".to_string(), node_names(&sections)),
+            ]));
+        }
+        writer.add_key("busbars", JsonValue::Array(busbars));
+
+        let mut components = vec![];
+        for c in self.components() {
+            let mut component = vec![
+                ("name".to_string(), c.name().into()),
+                (
+                    "type".to_string(),
+                    format!("{:?}", c.r#type()).as_str().into(),
+                ),
+            ];
+            let mut terminals = vec![];
+            let mut t = 0;
+            while let Ok(terminal) = c.terminal(t) {
+                terminals.push(match terminal.read().unwrap().get_node() {
+                    Ok(n) => self.node(n)?.name().into(),
+                    Err(_) => JsonValue::Null,
+                });
+                t += 1;
+            }
+            component.push(("terminals".to_string(), JsonValue::Array(terminals)));
+            if let Ok(pos) = c.position() {
+                let pos = pos.read().unwrap();
+                component.push(("position".to_string(), (&pos.position().to_string()).into()));
+                if !pos.operating_time().is_zero() {
+                    component.push((
+                        "operating_time".to_string(),
+                        pos.operating_time().as_secs_f64().into(),
+                    ));
+                }
+            }
+            if let Ok(v) = c.value() {
+                component.push(("value".to_string(), v.into()));
+            }
+            let parameters = c.parameters();
+            if !parameters.is_empty() {
+                let parameters = parameters
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value.into()))
+                    .collect();
+                component.push(("parameters".to_string(), JsonValue::Object(parameters)));
+            }
+            if let Ok(transformer) = c.transformer_parameters() {
+                let transformer = transformer.read().unwrap();
+                component.push((
+                    "vector_group".to_string(),
+                    transformer.vector_group().to_string().as_str().into(),
+                ));
+                let tap = transformer.tap_changer().position();
+                if tap != 0 {
+                    component.push(("tap_position".to_string(), (tap as f64).into()));
+                }
+            }
+            if let Ok(load) = c.load_parameters() {
+                component.push((
+                    "load_model".to_string(),
+                    format!("{:?}", load.read().unwrap().model())
+                        .as_str()
+                        .into(),
+                ));
+            }
+            let tags = c.tags().read().unwrap();
+            if !tags.is_empty() {
+                let tags = tags
+                    .tags()
+                    .iter()
+                    .map(|tag| {
+                        JsonValue::Object(vec![
+                            ("name".to_string(), tag.name().into()),
+                            (
+                                "kind".to_string(),
+                                format!("{:?}", tag.kind()).as_str().into(),
+                            ),
+                        ])
+                    })
+                    .collect();
+                component.push(("tags".to_string(), JsonValue::Array(tags)));
+            }
+            components.push(JsonValue::Object(component));
+        }
+        writer.add_key("components", JsonValue::Array(components));
+
+        let interlocks = self
+            .all_interlocks()
+            .iter()
+            .map(|rule| {
+                let mut interlock = vec![];
+                match rule {
+                    Interlock::BreakerOpen { switch, breaker } => {
+                        interlock.push(("type".to_string(), "BreakerOpen".into()));
+                        interlock.push(("switch".to_string(), switch.into()));
+                        interlock.push(("breaker".to_string(), breaker.into()));
+                    }
+                    Interlock::DeadNodes { switch } => {
+                        interlock.push(("type".to_string(), "DeadNodes".into()));
+                        interlock.push(("switch".to_string(), switch.into()));
+                    }
+                }
+                JsonValue::Object(interlock)
+            })
+            .collect();
+        writer.add_key("interlocks", JsonValue::Array(interlocks));
+
+        writer.write(w)
+    }
+}
+
+impl JsonReadable for Network {
+    fn read_json<R: Read>(r: R) -> Result<Box<Network>, Error> {
+        let reader = JsonReader::read(r)?;
+        let json = &reader.json;
+        if reader.get_str(json, "type")? != "Network" {
+            return Err(Error::InvalidJson("type Network expected".to_string()));
+        }
+        let net = Network::new(reader.get_str(json, "name")?);
+
+        for name in reader.get_strings(json, "nodes")? {
+            net.create_node(name)?;
+        }
+
+        for name in reader.get_strings(json, "sources")? {
+            net.add_source(name)?;
+        }
+
+        if json.get("busbars").is_some() {
+            for busbar in reader.get_array(json, "busbars")? {
+                net.add_busbar(
+                    reader.get_str(busbar, "name")?,
+                    reader.get_f64(busbar, "rated_voltage")?,
+                    &reader.get_strings(busbar, "sections")?,
+                )?;
+            }
+        }
+
+        for component in reader.get_array(json, "components")? {
+            let name = reader.get_str(component, "name")?;
+            let r#type = reader.get_str(component, "type")?;
+            let id = match r#type {
+                "CircuitBreaker" => net.create_component::<CircuitBreaker>(name)?,
+                "Disconnector" => net.create_component::<Disconnector>(name)?,
+                "EarthingSwitch" => net.create_component::<EarthingSwitch>(name)?,
+                "VoltageTransformer" => net.create_component::<VoltageTransformer>(name)?,
+                // Transformers had three terminals before the two- and three-winding distinction
+                "Transformer"
+                    if !matches!(
+                        reader.get_array(component, "terminals")?.get(2),
+                        None | Some(JsonValue::Null)
+                    ) =>
+                {
+                    net.create_component::<ThreeWindingTransformer>(name)?
+                }
+                "Transformer" => net.create_component::<Transformer>(name)?,
+                "ThreeWindingTransformer" => {
+                    net.create_component::<ThreeWindingTransformer>(name)?
+                }
+                "Line" => net.create_component::<Line>(name)?,
+                "Cable" => net.create_component::<Cable>(name)?,
+                "Load" => net.create_component::<Load>(name)?,
+                "Generator" => net.create_component::<Generator>(name)?,
+                "ExternalGrid" => net.create_component::<ExternalGrid>(name)?,
+                _ => {
+                    return Err(Error::InvalidJson(format!(
+                        "unknown component type {}",
+                        r#type
+                    )))
+                }
+            };
+            let c = net.component(id)?;
+
+            for (t, node) in reader.get_array(component, "terminals")?.iter().enumerate() {
+                match node {
+                    JsonValue::Null => {}
+                    JsonValue::String(node) => net.connect(node, name, t)?,
+                    _ => {
+                        return Err(Error::InvalidJson(format!(
+                            "terminal {} of component {} is not a node name or null",
+                            t, name
+                        )))
+                    }
+                }
+            }
+            if component.get("position").is_some() {
+                match reader.get_str(component, "position")? {
+                    "Open" => {}
+                    "Closed" => c.close()?,
+                    "Intermediate" => c
+                        .position()?
+                        .write()
+                        .unwrap()
+                        .set(SwitchPosition::Intermediate),
+                    "Bad" => c.position()?.write().unwrap().set(SwitchPosition::Bad),
+                    pos => {
+                        return Err(Error::InvalidJson(format!(
+                            "unknown position {} of component {}",
+                            pos, name
+                        )))
+                    }
+                }
+            }
+            match component.get("operating_time") {
+                None | Some(JsonValue::Null) => {}
+                Some(t) => match t.as_f64().map(Duration::try_from_secs_f64) {
+                    Some(Ok(t)) => c.position()?.write().unwrap().set_operating_time(t),
+                    _ => {
+                        return Err(Error::InvalidJson(format!(
+                        "operating time of component {} is not a non-negative number of seconds",
+                        name
+                    )))
+                    }
+                },
+            }
+            match component.get("value") {
+                None | Some(JsonValue::Null) => {}
+                Some(JsonValue::Number(v)) => c.update(*v)?,
+                Some(_) => {
+                    return Err(Error::InvalidJson(format!(
+                        "value of component {} is not a number",
+                        name
+                    )))
+                }
+            }
+            match component.get("parameters") {
+                None | Some(JsonValue::Null) => {}
+                Some(JsonValue::Object(parameters)) => {
+                    for (parameter, value) in parameters {
+                        match value {
+                            JsonValue::Number(v) => c.set_parameter(parameter, *v)?,
+                            _ => {
+                                return Err(Error::InvalidJson(format!(
+                                    "parameter {} of component {} is not a number",
+                                    parameter, name
+                                )))
+                            }
+                        }
+                    }
+                }
+                Some(_) => {
+                    return Err(Error::InvalidJson(format!(
+                        "parameters of component {} is not an object",
+                        name
+                    )))
+                }
+            }
+            if component.get("vector_group").is_some() {
+                c.transformer_parameters()?
+                    .write()
+                    .unwrap()
+                    .set_vector_group(VectorGroup::parse(
+                        reader.get_str(component, "vector_group")?,
+                    )?)?;
+            }
+            match component.get("tap_position") {
+                None | Some(JsonValue::Null) => {}
+                Some(JsonValue::Number(t)) if t.fract() == 0.0 => c
+                    .transformer_parameters()?
+                    .write()
+                    .unwrap()
+                    .tap_changer_mut()
+                    .set_position(*t as i32)?,
+                Some(_) => {
+                    return Err(Error::InvalidJson(format!(
+                        "tap position of component {} is not a whole number",
+                        name
+                    )))
+                }
+            }
+            if component.get("load_model").is_some() {
+                let model = match reader.get_str(component, "load_model")? {
+                    "ConstantPower" => LoadModel::ConstantPower,
+                    "ConstantImpedance" => LoadModel::ConstantImpedance,
+                    model => {
+                        return Err(Error::InvalidJson(format!(
+                            "unknown load model {} of component {}",
+                            model, name
+                        )))
+                    }
+                };
+                c.load_parameters()?.write().unwrap().set_model(model);
+            }
+            if component.get("tags").is_some() {
+                for tag in reader.get_array(component, "tags")? {
+                    let kind = match reader.get_str(tag, "kind")? {
+                        "DoNotOperate" => TagKind::DoNotOperate,
+                        "LockedOpen" => TagKind::LockedOpen,
+                        "OutOfService" => TagKind::OutOfService,
+                        "UnderMaintenance" => TagKind::UnderMaintenance,
+                        kind => {
+                            return Err(Error::InvalidJson(format!(
+                                "unknown tag kind {} of component {}",
+                                kind, name
+                            )))
+                        }
+                    };
+                    net.add_tag(name, Tag::new(reader.get_str(tag, "name")?, kind))?;
+                }
+            }
+        }
+
+        if json.get("interlocks").is_some() {
+            for interlock in reader.get_array(json, "interlocks")? {
+                let switch = reader.get_str(interlock, "switch")?.to_string();
+                let rule = match reader.get_str(interlock, "type")? {
+                    "BreakerOpen" => Interlock::BreakerOpen {
+                        switch,
+                        breaker: reader.get_str(interlock, "breaker")?.to_string(),
+                    },
+                    "DeadNodes" => Interlock::DeadNodes { switch },
+                    r#type => {
+                        return Err(Error::InvalidJson(format!(
+                            "unknown interlock type {}",
+                            r#type
+                        )))
+                    }
+                };
+                net.add_interlock(rule)?;
+            }
+        }
+
+        net.clear_history();
+        net.clear_journal();
+        Ok(Box::new(net))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parameters::{LineParameters, LoadParameters};
+
+    #[test]
+    fn json_parse() {
+        let json = JsonValue::parse(
+            " {\"a\": [1, -2.5e3, true, false, null], \"b\": {\"c\": \"x,y: z\\n\\u00e9\\ud83d\\ude00\"}, \"d\": {}, \"e\": []} ",
+        )
+        .unwrap();
+        assert_eq!(
+            json.get("a").unwrap(),
+            &JsonValue::Array(vec![
+                JsonValue::Number(1.0),
+                JsonValue::Number(-2500.0),
+                JsonValue::Bool(true),
+                JsonValue::Bool(false),
+                JsonValue::Null
+            ])
+        );
+        assert_eq!(
+            json.get("b").unwrap().get("c").unwrap().as_str().unwrap(),
+            "x,y: z\n\u{e9}\u{1F600}"
+        );
+        assert_eq!(json.get("d").unwrap(), &JsonValue::Object(vec![]));
+        assert!(json.get("f").is_none());
+
+        assert_eq!(JsonValue::parse(&json.to_string()).unwrap(), json);
+    }
+
+    #[test]
+    fn json_parse_errors() {
+        let invalid = [
+            ("", 1, 1),
+            ("{", 1, 2),
+            ("{\"a\" 1}", 1, 6),
+            ("{\"a\": 1,}", 1, 9),
+            ("[1 2]", 1, 4),
+            ("[01]", 1, 3),
+            ("[1.]", 1, 4),
+            ("[-]", 1, 3),
+            ("tru", 1, 4),
+            ("{\n  \"a\": \"b\n\"}", 2, 10),
+            ("\"\\x\"", 1, 3),
+            ("\"\\ud83d\"", 1, 8),
+            ("{} []", 1, 4),
+            ("{\n\n    'a': 1}", 3, 5),
+        ];
+        for (json, line, column) in invalid.iter() {
+            let e = JsonValue::parse(json).unwrap_err();
+            assert_eq!((e.line(), e.column()), (*line, *column), "{}: {}", json, e);
+        }
+
+        let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(JsonValue::parse(&nested(MAX_DEPTH)).is_ok());
+        let e = JsonValue::parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert_eq!((e.line(), e.column()), (1, MAX_DEPTH + 1));
+        assert_eq!(e.message(), "Nesting deeper than 128 levels");
+        assert!(JsonValue::parse(&"{\"a\":".repeat(100_000)).is_err());
+    }
 
     #[test]
     fn json_invalid_files() {
-        // todo test reading invalid json files
+        let e = Network::from_json("i dont exist.json").err().unwrap();
+        assert!(matches!(e, Error::Io { .. }));
+        assert!(e.to_string().contains("i dont exist.json"), "{}", e);
+
+        let json = "{\n    \"name\": \"test_invalid\",\n    \"type\" \"Network\"\n}";
+        let e = Network::read_json(json.as_bytes()).err().unwrap();
+        match e {
+            Error::JsonSyntax(e) => assert_eq!((e.line(), e.column()), (3, 12)),
+            e => panic!("Unexpected error {}", e),
+        }
+
+        let json = "{\"name\": \"cb\", \"type\": 1}";
+        assert!(matches!(
+            CircuitBreaker::read_json(json.as_bytes()),
+            Err(Error::InvalidJson(_))
+        ));
+        assert!(matches!(
+            Network::read_json(json.as_bytes()),
+            Err(Error::InvalidJson(_))
+        ));
+
+        let net = Network::new("net");
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.set_operating_time("cb", Duration::from_millis(1500))
+            .unwrap();
+        let mut buf = vec![];
+        net.write_json(&mut buf).unwrap();
+        let json = String::from_utf8(buf).unwrap();
+        assert!(json.contains("\"operating_time\": 1.5"));
+        for t in ["1e400", "1e20", "-1"] {
+            let json = json.replace(
+                "\"operating_time\": 1.5",
+                &format!("\"operating_time\": {}", t),
+            );
+            assert!(
+                matches!(
+                    Network::read_json(json.as_bytes()),
+                    Err(Error::InvalidJson(_))
+                ),
+                "{}",
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn json_legacy_transformer() {
+        let json = r#"{"name": "legacy", "type": "Network", "nodes": ["n1", "n2", "n3"], "sources": [],
+            "components": [
+                {"name": "tf", "type": "Transformer", "terminals": ["n1", "n2", null]},
+                {"name": "tf3", "type": "Transformer", "terminals": ["n1", "n2", "n3"]}
+            ]}"#;
+        let net = Network::read_json(json.as_bytes()).unwrap();
+        assert_eq!(
+            net.get_component("tf").unwrap().r#type(),
+            ComponentType::Transformer
+        );
+        assert_eq!(
+            net.get_component("tf3").unwrap().r#type(),
+            ComponentType::ThreeWindingTransformer
+        );
+        assert_eq!(net.neighbours("n3").unwrap(), vec!["tf3"]);
+    }
+
+    #[test]
+    fn json_file() {
+        let path = std::env::temp_dir()
+            .join("hvnv_json_file_test")
+            .join("test_network.json");
+        let net = Network::new("test_network");
+        net.create_node("node").unwrap();
+        net.to_json(&path).unwrap();
+
+        let loaded = Network::from_json(&path).unwrap();
+        assert_eq!(loaded.name(), "test_network");
+        assert!(loaded.get_node("node").is_ok());
+        std::fs::remove_file(&path).unwrap();
     }
 
     #[test]
     fn json_component() {
         let cb: &dyn Component = &CircuitBreaker::new("test_cb");
-        cb.to_json().unwrap();
-        let cb = CircuitBreaker::from_json("test_cb").unwrap();
+        let mut buf = vec![];
+        cb.write_json(&mut buf).unwrap();
+        let cb = CircuitBreaker::read_json(buf.as_slice()).unwrap();
         assert_eq!(cb.name(), "test_cb");
         assert_eq!(cb.r#type(), ComponentType::CircuitBreaker);
 
         // todo test all components
     }
+
+    #[test]
+    fn json_network() {
+        let net = Network::new("test_network");
+        net.create_node("node 1, \"main\"").unwrap();
+        net.create_node("node2").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.create_component::<EarthingSwitch>("es").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.create_component::<Transformer>("tf").unwrap();
+        net.create_component::<Cable>("cable").unwrap();
+        net.create_component::<ThreeWindingTransformer>("tf3")
+            .unwrap();
+        net.create_component::<Load>("load").unwrap();
+        net.create_component::<Generator>("gen").unwrap();
+        net.create_component::<ExternalGrid>("grid").unwrap();
+        net.add_busbar("bb", 150.0, &["node2"]).unwrap();
+        net.connect("node 1, \"main\"", "cb", 0).unwrap();
+        net.connect("node2", "cb", 1).unwrap();
+        net.connect("node2", "tf", 1).unwrap();
+        net.connect("node2", "vt", 0).unwrap();
+        net.add_source("node 1, \"main\"").unwrap();
+        net.get_component("cb").unwrap().close().unwrap();
+        net.update("vt", -10.5e3).unwrap();
+        net.report_position("ds", SwitchPosition::Bad).unwrap();
+        net.set_operating_time("cb", Duration::from_millis(80))
+            .unwrap();
+        net.add_tag("es", Tag::new("T1", TagKind::LockedOpen))
+            .unwrap();
+        net.set_parameter("cable", "x", 0.12).unwrap();
+        net.set_parameter("cable", "length", 2.5).unwrap();
+        net.connect("node2", "load", 0).unwrap();
+        net.connect("node2", "tf3", 2).unwrap();
+        net.set_parameter("tf3", "uk13", 12.5).unwrap();
+        net.set_vector_group("tf3", "YNd5d5").unwrap();
+        net.set_vector_group("tf", "Dyn11").unwrap();
+        net.set_parameter("tf", "tap_min", -9.0).unwrap();
+        net.set_parameter("tf", "tap_step", 1.5).unwrap();
+        net.lower_tap("tf").unwrap();
+        net.set_parameter("load", "q", -4.5).unwrap();
+        net.set_load_model("load", LoadModel::ConstantImpedance)
+            .unwrap();
+        net.set_parameter("gen", "q_min", -30.0).unwrap();
+        net.set_parameter("grid", "sk", 8000.0).unwrap();
+        net.add_interlock(Interlock::BreakerOpen {
+            switch: "ds".to_string(),
+            breaker: "cb".to_string(),
+        })
+        .unwrap();
+        net.add_interlock(Interlock::DeadNodes {
+            switch: "es".to_string(),
+        })
+        .unwrap();
+        let mut buf = vec![];
+        net.write_json(&mut buf).unwrap();
+
+        let loaded = Network::read_json(buf.as_slice()).unwrap();
+        assert_eq!(loaded.name(), "test_network");
+        assert_eq!(loaded.node_count(), 2);
+        assert_eq!(loaded.component_count(), 10);
+        assert_eq!(loaded.sources()[0].name(), "node 1, \"main\"");
+        assert_eq!(loaded.all_interlocks(), net.all_interlocks());
+        assert!(loaded.journal().events().is_empty());
+        assert_eq!(
+            loaded.get_component("vt").unwrap().value().unwrap(),
+            -10.5e3
+        );
+        assert!(loaded.undo().is_err());
+        for c in net.components() {
+            let l = loaded.get_component(c.name()).unwrap();
+            assert_eq!(l.r#type(), c.r#type());
+            assert_eq!(
+                loaded.neighbours(l.name()).unwrap(),
+                net.neighbours(c.name()).unwrap()
+            );
+        }
+        assert!(loaded
+            .get_component("tf")
+            .unwrap()
+            .terminal(0)
+            .unwrap()
+            .read()
+            .unwrap()
+            .get_node()
+            .is_err());
+        assert_eq!(
+            loaded.neighbours("node2").unwrap(),
+            vec!["cb", "vt", "tf", "tf3", "load"]
+        );
+        assert!(loaded
+            .get_component("cb")
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_closed());
+        assert_eq!(
+            loaded
+                .get_component("ds")
+                .unwrap()
+                .position()
+                .unwrap()
+                .read()
+                .unwrap()
+                .position(),
+            SwitchPosition::Bad
+        );
+        assert_eq!(loaded.tags("es").unwrap(), net.tags("es").unwrap());
+        assert_eq!(
+            loaded.busbar("bb").unwrap().sections(),
+            &vec![loaded.node_id("node2").unwrap()]
+        );
+        assert_eq!(loaded.busbar("bb").unwrap().rated_voltage(), 150.0);
+        assert_eq!(
+            *loaded
+                .get_component("cable")
+                .unwrap()
+                .line_parameters()
+                .unwrap()
+                .read()
+                .unwrap(),
+            LineParameters::new(0.0, 0.12, 0.0, 2.5, 0.0).unwrap()
+        );
+        assert_eq!(
+            *loaded
+                .get_component("load")
+                .unwrap()
+                .load_parameters()
+                .unwrap()
+                .read()
+                .unwrap(),
+            LoadParameters::new(0.0, -4.5, LoadModel::ConstantImpedance).unwrap()
+        );
+        assert_eq!(loaded.neighbours("load").unwrap(), vec!["node2"]);
+        assert_eq!(
+            *loaded
+                .get_component("tf3")
+                .unwrap()
+                .transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap(),
+            *net.get_component("tf3")
+                .unwrap()
+                .transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+        );
+        assert_eq!(
+            loaded
+                .get_component("tf")
+                .unwrap()
+                .transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .vector_group()
+                .to_string(),
+            "Dyn11"
+        );
+        assert_eq!(loaded.tap_position("tf").unwrap(), -1);
+        assert_eq!(
+            loaded
+                .get_component("tf")
+                .unwrap()
+                .transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .tap_changer(),
+            net.get_component("tf")
+                .unwrap()
+                .transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .tap_changer()
+        );
+        assert_eq!(
+            loaded
+                .get_component("gen")
+                .unwrap()
+                .parameter("q_min")
+                .unwrap(),
+            -30.0
+        );
+        assert_eq!(
+            loaded.get_component("grid").unwrap().r#type(),
+            ComponentType::ExternalGrid
+        );
+        assert_eq!(
+            loaded
+                .get_component("grid")
+                .unwrap()
+                .parameter("sk")
+                .unwrap(),
+            8000.0
+        );
+        assert_eq!(
+            loaded.operating_time("cb").unwrap(),
+            Duration::from_millis(80)
+        );
+        assert_eq!(
+            loaded.get_component("vt").unwrap().value().unwrap(),
+            -10.5e3
+        );
+    }
 }
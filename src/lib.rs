@@ -4,19 +4,47 @@
 #![warn(missing_docs)]
 #![warn(rustdoc::broken_intra_doc_links)]
 
+/// Network analysis: energization state of nodes and components
+pub mod analysis;
+/// Busbars; the main buses of voltage levels, with sections and couplers
+pub mod busbar;
 /// CLI interface to interact with the component, node and network APIs
 pub mod cli;
 /// Component API
 pub mod component;
+/// Crate-wide error type
+pub mod error;
+/// Undo/redo history of network changes
+mod history;
+/// Interlocking rules guarding switchgear operations
+pub mod interlock;
+/// Timestamped event journal of network changes
+pub mod journal;
+/// Save/Load Networks, Nodes and Components as json files or streams
+pub mod json;
 /// Measurement struct
 pub mod measurement;
 /// Network API
 pub mod network;
 /// Node API
 pub mod node;
+/// Callbacks on network state changes
+pub mod observer;
+/// Electrical parameters of components
+pub mod parameters;
 /// Switchgear Position struct
 pub mod position;
+/// Isolation and earthing switching programs
+pub mod procedure;
+/// Switching sequences with dry-run validation
+pub mod sequence;
+/// Simulation clock and in-flight switchgear operations
+pub mod simulation;
+/// Operator safety tags on components
+pub mod tag;
+/// On-load tap changers of transformers
+pub mod tap;
 /// Component Terminal struct
 pub mod terminal;
-/// Save/Load Networks, Nodes and Components as json files
-pub mod json;
+/// Topology processor; reduces a network to a bus-branch model
+pub mod topology;
@@ -20,6 +20,12 @@ impl Measurement {
     }
 }
 
+impl Default for Measurement {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,15 +1,854 @@
-use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt;
-use std::rc::Rc;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use std::time::Duration;
 
+use super::busbar::Busbar;
 use super::component::*;
+use super::error::Error;
+use super::history::{Edit, History};
+use super::interlock::{Interlock, Operation};
+use super::journal::{Event, EventKind, EventSource, Journal};
 use super::node::*;
+use super::observer::{Notification, Observers, Subscription, TopologyChange};
+use super::parameters::{LoadModel, VectorGroup};
+use super::position::SwitchPosition;
+use super::simulation::InFlight;
+use super::tag::{Tag, TagKind};
+
+/// How to handle connections when removing a node or component from a [Network]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RemoveMode {
+    /// Disconnect every connected node or component before removing
+    Cascade,
+    /// Refuse to remove a node or component that is still connected
+    RefuseIfConnected,
+}
+
+/// Stable identifier of a node within a [Network]; never reused after the node is removed
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct NodeId(usize);
+
+/// Stable identifier of a component within a [Network]; never reused after the component is removed
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ComponentId(usize);
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+impl fmt::Display for ComponentId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Network element a name refers to
+#[derive(Clone, Copy)]
+enum Element {
+    Node(NodeId),
+    Component(ComponentId),
+}
+
+/// Arena storage of a network. Slots of removed elements are left empty so ids stay stable.
+/// Components know the node connected to each of their terminals; the graph keeps the reverse adjacency.
+/// All changes are made by applying an [Edit], so they can be undone.
+#[derive(Default)]
+pub(crate) struct Graph {
+    nodes: Vec<Option<Arc<Node>>>,
+    adjacency: Vec<Vec<ComponentId>>,
+    components: Vec<Option<Arc<dyn Component>>>,
+    names: HashMap<String, Element>,
+    sources: Vec<NodeId>,
+    busbars: Vec<Busbar>,
+    interlocks: Vec<Interlock>,
+    node_count: usize,
+    component_count: usize,
+    history: History,
+    journal: Journal,
+    source: EventSource,
+    pending: Vec<Notification>,
+    time: Duration,
+    in_flight: Vec<InFlight>,
+}
+
+impl Graph {
+    pub(crate) fn node(&self, id: NodeId) -> Result<&Arc<Node>, Error> {
+        match self.nodes.get(id.0) {
+            Some(Some(n)) => Ok(n),
+            _ => Err(Error::NotFound {
+                kind: "node",
+                name: id.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn component(&self, id: ComponentId) -> Result<&Arc<dyn Component>, Error> {
+        match self.components.get(id.0) {
+            Some(Some(c)) => Ok(c),
+            _ => Err(Error::NotFound {
+                kind: "component",
+                name: id.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn node_id(&self, name: &str) -> Result<NodeId, Error> {
+        match self.names.get(name) {
+            Some(Element::Node(id)) => Ok(*id),
+            _ => Err(Error::NotFound {
+                kind: "node",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn component_id(&self, name: &str) -> Result<ComponentId, Error> {
+        match self.names.get(name) {
+            Some(Element::Component(id)) => Ok(*id),
+            _ => Err(Error::NotFound {
+                kind: "component",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn node_ids(&self) -> Vec<NodeId> {
+        (0..self.nodes.len())
+            .filter(|i| self.nodes[*i].is_some())
+            .map(NodeId)
+            .collect()
+    }
+
+    pub(crate) fn node_components(&self, id: NodeId) -> Result<&Vec<ComponentId>, Error> {
+        self.node(id)?;
+        Ok(&self.adjacency[id.0])
+    }
+
+    pub(crate) fn components(&self) -> impl Iterator<Item = &Arc<dyn Component>> {
+        self.components.iter().flatten()
+    }
+
+    pub(crate) fn sources(&self) -> &Vec<NodeId> {
+        &self.sources
+    }
+
+    /// Check the interlocking rules guarding a switch, and that closing it does not short an energized node to earth
+    fn check_operate(&self, id: ComponentId, operation: Operation) -> Result<(), Error> {
+        let c = self.component(id)?;
+        for rule in self.interlocks.iter().filter(|x| x.switch() == c.name()) {
+            rule.check_graph(self, operation)?;
+        }
+        if operation == Operation::Close && c.position()?.read().unwrap().is_open() {
+            self.check_earthing(id)?;
+        }
+        Ok(())
+    }
+
+    /// Check if a given name already exists in the network. Used to enforce unique names between all nodes and components within the network.
+    fn check_name(&self, name: &str) -> Result<(), Error> {
+        match self.names.get(name) {
+            None => Ok(()),
+            Some(_) => Err(Error::NameInUse(name.to_string())),
+        }
+    }
+
+    /// Connect a component terminal to a node, see [Network::connect]
+    fn connect(
+        &mut self,
+        node: NodeId,
+        component: ComponentId,
+        terminal: usize,
+    ) -> Result<(), Error> {
+        let c = self.component(component)?.clone();
+        if c.nodes().contains(&node) {
+            return Err(Error::AlreadyConnected {
+                component: c.name().clone(),
+                node: self.node(node)?.name().clone(),
+            });
+        }
+        self.node(node)?;
+        c.terminal(terminal)?.write().unwrap().connect(node)?;
+        self.adjacency[node.0].push(component);
+        Ok(())
+    }
+
+    /// Disconnect a component from a node, see [Network::disconnect]
+    fn disconnect(&mut self, node: NodeId, component: ComponentId) -> Result<(), Error> {
+        let c = self.component(component)?.clone();
+        let mut i = 0;
+        while let Ok(t) = c.terminal(i) {
+            let mut t = t.write().unwrap();
+            if t.get_node().ok() == Some(node) {
+                t.disconnect()?;
+                self.adjacency[node.0].retain(|x| *x != component);
+                return Ok(());
+            }
+            i += 1;
+        }
+        Err(Error::NotConnected {
+            component: c.name().clone(),
+            node: self.node(node)?.name().clone(),
+        })
+    }
+
+    /// Return the index of the component terminal that is connected to the node
+    fn terminal_of(&self, node: NodeId, component: ComponentId) -> Result<usize, Error> {
+        let c = self.component(component)?;
+        let mut i = 0;
+        while let Ok(t) = c.terminal(i) {
+            if t.read().unwrap().get_node().ok() == Some(node) {
+                return Ok(i);
+            }
+            i += 1;
+        }
+        Err(Error::NotConnected {
+            component: c.name().clone(),
+            node: self.node(node)?.name().clone(),
+        })
+    }
+
+    /// Return the busbar the node is a section of, if any
+    pub(crate) fn busbar_of(&self, node: NodeId) -> Option<&Busbar> {
+        self.busbars.iter().find(|b| b.sections().contains(&node))
+    }
+
+    /// Return the busbar with the given name
+    fn busbar(&self, name: &str) -> Result<&Busbar, Error> {
+        match self.busbars.iter().find(|b| b.name() == name) {
+            Some(b) => Ok(b),
+            None => Err(Error::NotFound {
+                kind: "busbar",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Return the edits that remove a node; connected components are handled according to the [RemoveMode]
+    fn remove_node_edits(&self, id: NodeId, mode: RemoveMode) -> Result<Vec<Edit>, Error> {
+        let components = self.adjacency[id.0].clone();
+        if mode == RemoveMode::RefuseIfConnected && !components.is_empty() {
+            return Err(Error::StillConnected(self.node(id)?.name().clone()));
+        }
+        let mut edits = vec![];
+        for c in components {
+            edits.push(Edit::Disconnect {
+                node: id,
+                component: c,
+                terminal: self.terminal_of(id, c)?,
+            });
+        }
+        if self.sources.contains(&id) {
+            edits.push(Edit::RemoveSource(id));
+        }
+        if let Some(busbar) = self.busbar_of(id) {
+            edits.push(Edit::RemoveBusbar(busbar.clone()));
+            if busbar.sections().len() > 1 {
+                edits.push(Edit::AddBusbar(busbar.without(id)));
+            }
+        }
+        edits.push(Edit::DeleteNode {
+            id,
+            node: self.node(id)?.clone(),
+        });
+        Ok(edits)
+    }
+
+    /// Describe the change an edit makes, for the journal. Batches are described by their edits.
+    fn event(&self, edit: &Edit) -> Option<Event> {
+        let name = |id: ComponentId| match self.component(id) {
+            Ok(c) => c.name().clone(),
+            Err(_) => id.to_string(),
+        };
+        let node_name = |id: NodeId| match self.node(id) {
+            Ok(n) => n.name().clone(),
+            Err(_) => id.to_string(),
+        };
+        let (kind, name, description) = match edit {
+            Edit::InsertNode { node, .. } => (
+                EventKind::Topology,
+                node.name().clone(),
+                "Created node".to_string(),
+            ),
+            Edit::DeleteNode { node, .. } => (
+                EventKind::Topology,
+                node.name().clone(),
+                "Removed node".to_string(),
+            ),
+            Edit::InsertComponent { component, .. } => (
+                EventKind::Topology,
+                component.name().clone(),
+                format!("Created {}", component.r#type()),
+            ),
+            Edit::DeleteComponent { component, .. } => (
+                EventKind::Topology,
+                component.name().clone(),
+                format!("Removed {}", component.r#type()),
+            ),
+            Edit::Connect {
+                node,
+                component,
+                terminal,
+            } => (
+                EventKind::Topology,
+                name(*component),
+                format!(
+                    "Connected terminal {} to node {}",
+                    terminal,
+                    node_name(*node)
+                ),
+            ),
+            Edit::Disconnect {
+                node,
+                component,
+                terminal,
+            } => (
+                EventKind::Topology,
+                name(*component),
+                format!(
+                    "Disconnected terminal {} from node {}",
+                    terminal,
+                    node_name(*node)
+                ),
+            ),
+            Edit::Open(id) => (EventKind::Position, name(*id), "Opened".to_string()),
+            Edit::Close(id) => (EventKind::Position, name(*id), "Closed".to_string()),
+            Edit::Position { component, to, .. } => (
+                EventKind::Position,
+                name(*component),
+                format!("Reported {}", to),
+            ),
+            Edit::Start(operation) => (
+                EventKind::Position,
+                name(operation.component()),
+                format!(
+                    "Started to {}, completes at {:.3} s",
+                    operation.operation(),
+                    operation.completes().as_secs_f64()
+                ),
+            ),
+            Edit::Finish {
+                operation,
+                complete,
+            } => {
+                let description = match (operation.operation(), complete) {
+                    (Operation::Open, true) => "Opened".to_string(),
+                    (Operation::Close, true) => "Closed".to_string(),
+                    (op, false) => format!("Cancelled {} operation", op),
+                };
+                (
+                    EventKind::Position,
+                    name(operation.component()),
+                    description,
+                )
+            }
+            Edit::OperatingTime { component, to, .. } => (
+                EventKind::Configuration,
+                name(*component),
+                format!("Operating time set to {:.3} s", to.as_secs_f64()),
+            ),
+            Edit::AddTag { component, tag } => (
+                EventKind::Configuration,
+                name(*component),
+                format!("Placed tag {}", tag),
+            ),
+            Edit::RemoveTag { component, tag } => (
+                EventKind::Configuration,
+                name(*component),
+                format!("Removed tag {}", tag),
+            ),
+            Edit::Parameter {
+                component,
+                name: parameter,
+                from,
+                to,
+            } => (
+                EventKind::Configuration,
+                name(*component),
+                format!("Changed {} from {} to {}", parameter, from, to),
+            ),
+            Edit::Tap {
+                component,
+                from,
+                to,
+            } => (
+                EventKind::Position,
+                name(*component),
+                format!(
+                    "Tap {} to {}",
+                    if to > from { "raised" } else { "lowered" },
+                    to
+                ),
+            ),
+            Edit::VectorGroup { component, to, .. } => (
+                EventKind::Configuration,
+                name(*component),
+                format!("Vector group set to {}", to),
+            ),
+            Edit::LoadModel { component, to, .. } => (
+                EventKind::Configuration,
+                name(*component),
+                format!("Load model set to {}", to),
+            ),
+            Edit::Update {
+                component,
+                from,
+                to,
+            } => (
+                EventKind::Measurement,
+                name(*component),
+                format!("Updated {} to {}", from, to),
+            ),
+            Edit::AddSource(id) => (
+                EventKind::Configuration,
+                node_name(*id),
+                "Designated as source".to_string(),
+            ),
+            Edit::RemoveSource(id) => (
+                EventKind::Configuration,
+                node_name(*id),
+                "Removed source designation".to_string(),
+            ),
+            Edit::AddBusbar(busbar) => (
+                EventKind::Configuration,
+                busbar.name().clone(),
+                format!(
+                    "Added busbar at {} kV with sections {}",
+                    busbar.rated_voltage(),
+                    busbar
+                        .sections()
+                        .iter()
+                        .map(|s| node_name(*s))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            ),
+            Edit::RemoveBusbar(busbar) => (
+                EventKind::Configuration,
+                busbar.name().clone(),
+                "Removed busbar".to_string(),
+            ),
+            Edit::AddInterlock(rule) => (
+                EventKind::Configuration,
+                rule.switch().clone(),
+                format!("Added interlock: {}", rule),
+            ),
+            Edit::RemoveInterlock(rule) => (
+                EventKind::Configuration,
+                rule.switch().clone(),
+                format!("Removed interlock: {}", rule),
+            ),
+            Edit::Clock { .. } | Edit::Batch(_) => return None,
+        };
+        // An operation completes at its own time, which may lie before the end of the advance that completes it
+        let sim_time = match edit {
+            Edit::Finish {
+                operation,
+                complete: true,
+            } => operation.completes(),
+            _ => self.time,
+        };
+        Some(Event::new(
+            sim_time,
+            self.source.clone(),
+            kind,
+            &name,
+            &description,
+        ))
+    }
+
+    /// Describe the change an edit makes, for the observers. Batches are described by their edits.
+    fn notification(&self, edit: &Edit) -> Option<Notification> {
+        let name = |id: ComponentId| match self.component(id) {
+            Ok(c) => c.name().clone(),
+            Err(_) => id.to_string(),
+        };
+        let topology =
+            |name: String, change: TopologyChange| Some(Notification::Topology { name, change });
+        match edit {
+            Edit::InsertNode { node, .. } => {
+                topology(node.name().clone(), TopologyChange::NodeCreated)
+            }
+            Edit::DeleteNode { node, .. } => {
+                topology(node.name().clone(), TopologyChange::NodeRemoved)
+            }
+            Edit::InsertComponent { component, .. } => {
+                topology(component.name().clone(), TopologyChange::ComponentCreated)
+            }
+            Edit::DeleteComponent { component, .. } => {
+                topology(component.name().clone(), TopologyChange::ComponentRemoved)
+            }
+            Edit::Connect {
+                node,
+                component,
+                terminal,
+            } => topology(
+                name(*component),
+                TopologyChange::Connected {
+                    terminal: *terminal,
+                    node: self.node(*node).ok()?.name().clone(),
+                },
+            ),
+            Edit::Disconnect {
+                node,
+                component,
+                terminal,
+            } => topology(
+                name(*component),
+                TopologyChange::Disconnected {
+                    terminal: *terminal,
+                    node: self.node(*node).ok()?.name().clone(),
+                },
+            ),
+            Edit::Open(id) => Some(Notification::Position {
+                name: name(*id),
+                old: SwitchPosition::Closed,
+                new: SwitchPosition::Open,
+            }),
+            Edit::Close(id) => Some(Notification::Position {
+                name: name(*id),
+                old: SwitchPosition::Open,
+                new: SwitchPosition::Closed,
+            }),
+            Edit::Position {
+                component,
+                from,
+                to,
+            } => Some(Notification::Position {
+                name: name(*component),
+                old: *from,
+                new: *to,
+            }),
+            Edit::Start(operation) => Some(Notification::Position {
+                name: name(operation.component()),
+                old: operation.start(),
+                new: SwitchPosition::Intermediate,
+            }),
+            Edit::Finish {
+                operation,
+                complete,
+            } => Some(Notification::Position {
+                name: name(operation.component()),
+                old: SwitchPosition::Intermediate,
+                new: operation.end(*complete),
+            }),
+            Edit::Update {
+                component,
+                from,
+                to,
+            } => Some(Notification::Measurement {
+                name: name(*component),
+                old: *from,
+                new: *to,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Apply an edit, journal the change and queue it for the observers; returns an error without applying it if it is not valid for the current graph
+    fn apply(&mut self, edit: &Edit) -> Result<(), Error> {
+        let event = self.event(edit);
+        let notification = self.notification(edit);
+        match edit {
+            Edit::InsertNode { id, node } => {
+                self.check_name(node.name())?;
+                if id.0 == self.nodes.len() {
+                    self.nodes.push(None);
+                    self.adjacency.push(vec![]);
+                }
+                self.nodes[id.0] = Some(node.clone());
+                self.names.insert(node.name().clone(), Element::Node(*id));
+                self.node_count += 1;
+            }
+            Edit::DeleteNode { id, node } => {
+                self.node(*id)?;
+                if !self.adjacency[id.0].is_empty() {
+                    return Err(Error::StillConnected(node.name().clone()));
+                }
+                self.nodes[id.0] = None;
+                self.names.remove(node.name());
+                self.node_count -= 1;
+            }
+            Edit::InsertComponent { id, component } => {
+                self.check_name(component.name())?;
+                if id.0 == self.components.len() {
+                    self.components.push(None);
+                }
+                self.components[id.0] = Some(component.clone());
+                self.names
+                    .insert(component.name().clone(), Element::Component(*id));
+                self.component_count += 1;
+            }
+            Edit::DeleteComponent { id, component } => {
+                self.component(*id)?;
+                if !component.nodes().is_empty() {
+                    return Err(Error::StillConnected(component.name().clone()));
+                }
+                self.components[id.0] = None;
+                self.names.remove(component.name());
+                self.component_count -= 1;
+            }
+            Edit::Connect {
+                node,
+                component,
+                terminal,
+            } => self.connect(*node, *component, *terminal)?,
+            Edit::Disconnect {
+                node, component, ..
+            } => self.disconnect(*node, *component)?,
+            Edit::Open(id) => self.component(*id)?.open()?,
+            Edit::Close(id) => self.component(*id)?.close()?,
+            Edit::Position { component, to, .. } => self
+                .component(*component)?
+                .position()?
+                .write()
+                .unwrap()
+                .set(*to),
+            Edit::Start(operation) => {
+                let c = self.component(operation.component())?;
+                c.position()?
+                    .write()
+                    .unwrap()
+                    .set(SwitchPosition::Intermediate);
+                self.in_flight.push(*operation);
+            }
+            Edit::Finish {
+                operation,
+                complete,
+            } => match self.in_flight.iter().position(|x| x == operation) {
+                Some(i) => {
+                    let c = self.component(operation.component())?;
+                    c.position()?.write().unwrap().set(operation.end(*complete));
+                    self.in_flight.remove(i);
+                }
+                None => {
+                    return Err(Error::NotFound {
+                        kind: "operation",
+                        name: operation.to_string(),
+                    })
+                }
+            },
+            Edit::Clock { to, .. } => self.time = *to,
+            Edit::OperatingTime { component, to, .. } => self
+                .component(*component)?
+                .position()?
+                .write()
+                .unwrap()
+                .set_operating_time(*to),
+            Edit::AddTag { component, tag } => {
+                let c = self.component(*component)?;
+                if tag.kind() == TagKind::LockedOpen
+                    && !matches!(c.position().map(|p| p.read().unwrap().is_open()), Ok(true))
+                {
+                    return Err(Error::InvalidArgument(format!(
+                        "Can not place {} on {}, it is not open switchgear",
+                        tag,
+                        c.name()
+                    )));
+                }
+                c.tags().write().unwrap().add(tag.clone())?
+            }
+            Edit::RemoveTag { component, tag } => {
+                self.component(*component)?
+                    .tags()
+                    .write()
+                    .unwrap()
+                    .remove(tag.name())?;
+            }
+            Edit::Parameter {
+                component,
+                name,
+                to,
+                ..
+            } => self.component(*component)?.set_parameter(name, *to)?,
+            Edit::Tap { component, to, .. } => self
+                .component(*component)?
+                .transformer_parameters()?
+                .write()
+                .unwrap()
+                .tap_changer_mut()
+                .set_position(*to)?,
+            Edit::VectorGroup { component, to, .. } => self
+                .component(*component)?
+                .transformer_parameters()?
+                .write()
+                .unwrap()
+                .set_vector_group(to.clone())?,
+            Edit::LoadModel { component, to, .. } => self
+                .component(*component)?
+                .load_parameters()?
+                .write()
+                .unwrap()
+                .set_model(*to),
+            Edit::Update { component, to, .. } => self.component(*component)?.update(*to)?,
+            Edit::AddSource(id) => {
+                let node = self.node(*id)?;
+                if self.sources.contains(id) {
+                    return Err(Error::AlreadySource(node.name().clone()));
+                }
+                self.sources.push(*id);
+            }
+            Edit::RemoveSource(id) => match self.sources.iter().position(|x| x == id) {
+                Some(i) => {
+                    self.sources.remove(i);
+                }
+                None => {
+                    return Err(Error::NotFound {
+                        kind: "source",
+                        name: id.to_string(),
+                    })
+                }
+            },
+            Edit::AddBusbar(busbar) => {
+                if self.busbar(busbar.name()).is_ok() {
+                    return Err(Error::InvalidArgument(format!(
+                        "Busbar {} already exists",
+                        busbar.name()
+                    )));
+                }
+                if busbar.sections().is_empty() {
+                    return Err(Error::InvalidArgument(format!(
+                        "Busbar {} needs at least one section",
+                        busbar.name()
+                    )));
+                }
+                for s in busbar.sections() {
+                    let node = self.node(*s)?;
+                    if let Some(other) = self.busbar_of(*s) {
+                        return Err(Error::InvalidArgument(format!(
+                            "Node {} is already a section of busbar {}",
+                            node.name(),
+                            other.name()
+                        )));
+                    }
+                }
+                self.busbars.push(busbar.clone());
+            }
+            Edit::RemoveBusbar(busbar) => match self.busbars.iter().position(|x| x == busbar) {
+                Some(i) => {
+                    self.busbars.remove(i);
+                }
+                None => {
+                    return Err(Error::NotFound {
+                        kind: "busbar",
+                        name: busbar.name().clone(),
+                    })
+                }
+            },
+            Edit::AddInterlock(rule) => {
+                if self.interlocks.contains(rule) {
+                    return Err(Error::InvalidArgument(format!(
+                        "Interlock already exists: {}",
+                        rule
+                    )));
+                }
+                self.interlocks.push(rule.clone());
+            }
+            Edit::RemoveInterlock(rule) => match self.interlocks.iter().position(|x| x == rule) {
+                Some(i) => {
+                    self.interlocks.remove(i);
+                }
+                None => {
+                    return Err(Error::NotFound {
+                        kind: "interlock",
+                        name: rule.to_string(),
+                    })
+                }
+            },
+            Edit::Batch(edits) => {
+                let journaled = self.journal.events().len();
+                let pending = self.pending.len();
+                for (i, e) in edits.iter().enumerate() {
+                    if let Err(error) = self.apply(e) {
+                        // Roll back the edits applied so far, so a batch is applied entirely or not at all
+                        let rollback = edits[..i]
+                            .iter()
+                            .rev()
+                            .try_for_each(|e| self.apply(&e.inverse()));
+                        self.journal.truncate(journaled);
+                        self.pending.truncate(pending);
+                        rollback?;
+                        return Err(error);
+                    }
+                }
+            }
+        }
+        if let Some(event) = event {
+            self.journal.record(event);
+        }
+        if let Some(notification) = notification {
+            self.pending.push(notification);
+        }
+        Ok(())
+    }
+
+    /// Apply an edit and record it in the history
+    fn execute(&mut self, edit: Edit) -> Result<(), Error> {
+        self.apply(&edit)?;
+        self.history.record(edit);
+        Ok(())
+    }
+
+    /// Raise or lower the tap position of a transformer
+    fn tap(&mut self, name: &str, raise: bool) -> Result<(), Error> {
+        let component = self.component_id(name)?;
+        let edit = {
+            let c = self.component(component)?;
+            let parameters = c.transformer_parameters()?.read().unwrap();
+            c.tags().read().unwrap().check(c.name())?;
+            let tap = parameters.tap_changer();
+            Edit::Tap {
+                component,
+                from: tap.position(),
+                to: if raise {
+                    tap.check_raise()?
+                } else {
+                    tap.check_lower()?
+                },
+            }
+        };
+        self.execute(edit)
+    }
+
+    /// Operate switchgear; immediately if it has no operating time, otherwise start an operation that completes after it
+    fn operate(&mut self, id: ComponentId, operation: Operation) -> Result<(), Error> {
+        let edit = {
+            let c = self.component(id)?;
+            let pos = c.position()?.read().unwrap();
+            c.tags().read().unwrap().check(c.name())?;
+            match operation {
+                Operation::Open => pos.check_open()?,
+                Operation::Close => pos.check_close()?,
+            }
+            if pos.operating_time().is_zero() {
+                match operation {
+                    Operation::Open => Edit::Open(id),
+                    Operation::Close => Edit::Close(id),
+                }
+            } else {
+                Edit::Start(InFlight::new(
+                    id,
+                    operation,
+                    later(self.time, pos.operating_time())?,
+                ))
+            }
+        };
+        self.execute(edit)
+    }
+}
 
 /// A network which contains nodes and components
+///
+/// Nodes and components are stored in an arena and identified by a stable [NodeId] or [ComponentId].
+/// Lookups by id or by name take constant time.
+/// Every change made through the network API is recorded and can be reverted with [Network::undo].
+/// The network is thread-safe; share it between threads with an [Arc].
 pub struct Network {
     name: String,
-    nodes: RefCell<Vec<Rc<Node>>>,
-    components: RefCell<Vec<Rc<dyn Component>>>,
+    graph: RwLock<Graph>,
+    observers: RwLock<Observers>,
 }
 
 impl Network {
@@ -17,8 +856,8 @@ impl Network {
     pub fn new(name: &str) -> Network {
         Network {
             name: name.to_string(),
-            nodes: RefCell::new(vec![]),
-            components: RefCell::new(vec![]),
+            graph: RwLock::new(Graph::default()),
+            observers: RwLock::new(Observers::default()),
         }
     }
 
@@ -27,188 +866,816 @@ impl Network {
         &self.name
     }
 
-    /// Check if a given name already exists in the network. Used to enforce unique names between all nodes and components within the network.
-    fn check_name(&self, name: &str) -> Result<(), ()> {
-        let node_index = self.nodes.borrow().iter().position(|x| x.name() == name);
-        let component_index = self
-            .components
-            .borrow()
-            .iter()
-            .position(|x| x.name() == name);
-        match (node_index, component_index) {
-            (Some(_), Some(_)) => Err(()),
-            (Some(_), None) => Err(()),
-            (None, Some(_)) => Err(()),
-            (None, None) => Ok(()),
-        }
+    /// Lock the graph for reading
+    pub(crate) fn graph(&self) -> RwLockReadGuard<'_, Graph> {
+        self.graph.read().unwrap()
     }
 
-    /// Create a node with the given name if the name is not already used in this network
-    pub fn create_node(&self, name: &str) -> Result<(), String> {
-        match self.check_name(name) {
-            Err(_) => Err(format!("Failed to create node {} - A node or component with this name already exists in network {}", name, self.name())),
-            Ok(()) => {
-                let n = Rc::new(Node::new(name));
-                self.nodes.borrow_mut().push(n);
-                Ok(())
+    /// Make a change to the graph, then notify the observers once the graph is unlocked again
+    fn change<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut Graph) -> Result<T, Error>,
+    {
+        let result = f(&mut self.graph.write().unwrap());
+        let pending = std::mem::take(&mut self.graph.write().unwrap().pending);
+        if !pending.is_empty() {
+            let observers = self.observers.read().unwrap().clone();
+            for n in &pending {
+                observers.notify(n);
             }
         }
+        result
     }
 
-    /// Remove a node with the given name from the network, if it exists
-    pub fn remove_node(&self, name: &str) -> Result<(), String> {
-        let index = self.nodes.borrow().iter().position(|x| x.name() == name);
-        match index {
-            Some(i) => {
-                self.nodes.borrow_mut().remove(i);
-                Ok(())
-            }
-            None => Err(format!(
-                "Failed to remove node {} - No node with this name exists in network {}",
-                name,
-                self.name()
-            )),
-        }
+    /// Create a node with the given name if the name is not already used in this network
+    pub fn create_node(&self, name: &str) -> Result<NodeId, Error> {
+        self.change(|g| {
+            let id = NodeId(g.nodes.len());
+            g.execute(Edit::InsertNode {
+                id,
+                node: Arc::new(Node::new(name)),
+            })?;
+            Ok(id)
+        })
+    }
+
+    /// Remove a node with the given name from the network, if it exists. Connected components are handled according to the [RemoveMode].
+    pub fn remove_node(&self, name: &str, mode: RemoveMode) -> Result<(), Error> {
+        self.change(|g| {
+            let id = g.node_id(name)?;
+            let edits = g.remove_node_edits(id, mode)?;
+            g.execute(Edit::Batch(edits))
+        })
     }
 
     /// Return the number of nodes in the network
     pub fn node_count(&self) -> usize {
-        self.nodes.borrow().len()
+        self.graph.read().unwrap().node_count
     }
 
     /// Print each node in the network
     pub fn list_nodes(&self) {
-        for node in self.nodes.borrow().iter() {
+        for node in self.nodes() {
             println!("<{}", node);
         }
     }
 
     /// Get a reference to the node with the given name if it exists
-    pub fn get_node(&self, name: &str) -> Result<Rc<Node>, String> {
-        let nodes = self.nodes.borrow();
-        let node = nodes.iter().find(|x| x.name() == name);
-        match node {
-            Some(node) => Ok(node.clone()),
-            None => Err(format!(
-                "Node with name {} does not exist in network {}",
-                name,
-                self.name()
-            )),
-        }
+    pub fn get_node(&self, name: &str) -> Result<Arc<Node>, Error> {
+        let g = self.graph.read().unwrap();
+        Ok(g.node(g.node_id(name)?)?.clone())
     }
 
-    /// Create a component of a given [ComponentType] with a given name, if the name is not already in use in this network
-    pub fn create_component<T: 'static + Component>(&self, name: &str) -> Result<(), String> {
-        match self.check_name(name) {
-            Err(_) => Err(format!("Failed to create component {} - A node or component with this name already exists in network {}", name, self.name())),
-            Ok(()) => {
-                let c = Rc::new(T::new(name));
-                self.components.borrow_mut().push(c);
-                Ok(())
-            }
-        }
+    /// Get the id of the node with the given name if it exists
+    pub fn node_id(&self, name: &str) -> Result<NodeId, Error> {
+        self.graph.read().unwrap().node_id(name)
+    }
+
+    /// Get a reference to the node with the given id if it exists
+    pub fn node(&self, id: NodeId) -> Result<Arc<Node>, Error> {
+        Ok(self.graph.read().unwrap().node(id)?.clone())
     }
 
-    /// Remove a component with the given name, if it exists in the network
-    pub fn remove_component(&self, name: &str) -> Result<(), String> {
-        let index = self
-            .components
-            .borrow()
+    /// Return a reference to each node in the network
+    pub fn nodes(&self) -> Vec<Arc<Node>> {
+        self.graph
+            .read()
+            .unwrap()
+            .nodes
             .iter()
-            .position(|x| x.name() == name);
-        match index {
-            Some(i) => {
-                self.components.borrow_mut().remove(i);
-                Ok(())
-            }
-            None => Err(format!(
-                "Failed to remove component {} - No component with this name exists in network {}",
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Return the id of each node in the network
+    pub fn node_ids(&self) -> Vec<NodeId> {
+        self.graph.read().unwrap().node_ids()
+    }
+
+    /// Return the id of each component connected to the node with the given id
+    pub fn node_components(&self, id: NodeId) -> Result<Vec<ComponentId>, Error> {
+        Ok(self.graph.read().unwrap().node_components(id)?.clone())
+    }
+
+    /// Designate the node with the given name as a source point, from which energization propagates.
+    /// The nodes of generators and external grids are energized without being designated.
+    pub fn add_source(&self, name: &str) -> Result<(), Error> {
+        self.change(|g| {
+            let id = g.node_id(name)?;
+            g.execute(Edit::AddSource(id))
+        })
+    }
+
+    /// Remove the source designation from the node with the given name
+    pub fn remove_source(&self, name: &str) -> Result<(), Error> {
+        self.change(|g| match g.node_id(name) {
+            Ok(id) if g.sources.contains(&id) => g.execute(Edit::RemoveSource(id)),
+            _ => Err(Error::NotFound {
+                kind: "source",
+                name: name.to_string(),
+            }),
+        })
+    }
+
+    /// Return a reference to each source node in the network
+    pub fn sources(&self) -> Vec<Arc<Node>> {
+        let g = self.graph.read().unwrap();
+        g.sources
+            .iter()
+            .filter_map(|id| g.node(*id).ok().cloned())
+            .collect()
+    }
+
+    /// Return the id of each source node in the network
+    pub fn source_ids(&self) -> Vec<NodeId> {
+        self.graph.read().unwrap().sources.clone()
+    }
+
+    /// Create a busbar with the given rated voltage in kV and number of sections. Returns the ids of the section nodes,
+    /// which are named after the busbar, or `<busbar>.1`, `<busbar>.2`, ... if there is more than one section.
+    pub fn create_busbar(
+        &self,
+        name: &str,
+        rated_voltage: f64,
+        sections: usize,
+    ) -> Result<Vec<NodeId>, Error> {
+        let names: Vec<String> = match sections {
+            1 => vec![name.to_string()],
+            _ => (1..=sections).map(|i| format!("{}.{}", name, i)).collect(),
+        };
+        self.change(|g| {
+            check_rated_voltage(rated_voltage)?;
+            if g.busbar(name).is_ok() {
+                return Err(Error::InvalidArgument(format!(
+                    "Busbar {} already exists",
+                    name
+                )));
+            }
+            if sections == 0 {
+                return Err(Error::InvalidArgument(format!(
+                    "Busbar {} needs at least one section",
+                    name
+                )));
+            }
+            let mut edits = vec![];
+            let mut ids = vec![];
+            for n in &names {
+                g.check_name(n)?;
+                let id = NodeId(g.nodes.len() + ids.len());
+                edits.push(Edit::InsertNode {
+                    id,
+                    node: Arc::new(Node::new(n)),
+                });
+                ids.push(id);
+            }
+            edits.push(Edit::AddBusbar(Busbar::new(
                 name,
-                self.name()
-            )),
-        }
+                rated_voltage,
+                ids.clone(),
+            )));
+            g.execute(Edit::Batch(edits))?;
+            Ok(ids)
+        })
+    }
+
+    /// Designate the existing nodes with the given names as the sections of a busbar with the given rated voltage in kV
+    pub fn add_busbar(
+        &self,
+        name: &str,
+        rated_voltage: f64,
+        sections: &[&str],
+    ) -> Result<(), Error> {
+        self.change(|g| {
+            check_rated_voltage(rated_voltage)?;
+            let mut ids = vec![];
+            for s in sections {
+                ids.push(g.node_id(s)?);
+            }
+            g.execute(Edit::AddBusbar(Busbar::new(name, rated_voltage, ids)))
+        })
+    }
+
+    /// Remove the busbar with the given name together with its section nodes. Connected components are handled according to the [RemoveMode].
+    pub fn remove_busbar(&self, name: &str, mode: RemoveMode) -> Result<(), Error> {
+        self.change(|g| {
+            let busbar = g.busbar(name)?.clone();
+            let mut edits = vec![Edit::RemoveBusbar(busbar.clone())];
+            for s in busbar.sections() {
+                edits.extend(
+                    g.remove_node_edits(*s, mode)?
+                        .into_iter()
+                        .filter(|e| !matches!(e, Edit::RemoveBusbar(_) | Edit::AddBusbar(_))),
+                );
+            }
+            g.execute(Edit::Batch(edits))
+        })
+    }
+
+    /// Return all busbars
+    pub fn busbars(&self) -> Vec<Busbar> {
+        self.graph.read().unwrap().busbars.clone()
+    }
+
+    /// Return the busbar with the given name
+    pub fn busbar(&self, name: &str) -> Result<Busbar, Error> {
+        self.graph.read().unwrap().busbar(name).cloned()
+    }
+
+    /// Return the busbar the node with the given id is a section of, if any
+    pub fn busbar_of(&self, id: NodeId) -> Option<Busbar> {
+        self.graph.read().unwrap().busbar_of(id).cloned()
+    }
+
+    /// Create a bus coupler; a circuit breaker with the given name between two busbar sections, which may belong to different busbars
+    pub fn add_coupler(
+        &self,
+        name: &str,
+        section_a: &str,
+        section_b: &str,
+    ) -> Result<ComponentId, Error> {
+        self.change(|g| {
+            let a = g.node_id(section_a)?;
+            let b = g.node_id(section_b)?;
+            if a == b || g.busbar_of(a).is_none() || g.busbar_of(b).is_none() {
+                return Err(Error::InvalidArgument(format!(
+                    "A coupler has to join two different busbar sections, not {} and {}",
+                    section_a, section_b
+                )));
+            }
+            g.check_name(name)?;
+            let id = ComponentId(g.components.len());
+            g.execute(Edit::Batch(vec![
+                Edit::InsertComponent {
+                    id,
+                    component: Arc::new(CircuitBreaker::new(name)),
+                },
+                Edit::Connect {
+                    node: a,
+                    component: id,
+                    terminal: 0,
+                },
+                Edit::Connect {
+                    node: b,
+                    component: id,
+                    terminal: 1,
+                },
+            ]))?;
+            Ok(id)
+        })
+    }
+
+    /// Create a component of a given [ComponentType] with a given name, if the name is not already in use in this network
+    pub fn create_component<T: 'static + Component>(
+        &self,
+        name: &str,
+    ) -> Result<ComponentId, Error> {
+        self.change(|g| {
+            let id = ComponentId(g.components.len());
+            g.execute(Edit::InsertComponent {
+                id,
+                component: Arc::new(T::new(name)),
+            })?;
+            Ok(id)
+        })
+    }
+
+    /// Remove a component with the given name, if it exists in the network. Connected nodes are handled according to the [RemoveMode].
+    pub fn remove_component(&self, name: &str, mode: RemoveMode) -> Result<(), Error> {
+        self.change(|g| {
+            let id = g.component_id(name)?;
+            let nodes = g.component(id)?.nodes();
+            if mode == RemoveMode::RefuseIfConnected && !nodes.is_empty() {
+                return Err(Error::StillConnected(name.to_string()));
+            }
+            let mut edits = vec![];
+            for n in nodes {
+                edits.push(Edit::Disconnect {
+                    node: n,
+                    component: id,
+                    terminal: g.terminal_of(n, id)?,
+                });
+            }
+            for rule in g.interlocks.iter().filter(|x| x.references(name)) {
+                edits.push(Edit::RemoveInterlock(rule.clone()));
+            }
+            for operation in g.in_flight.iter().filter(|x| x.component() == id) {
+                edits.push(Edit::Finish {
+                    operation: *operation,
+                    complete: false,
+                });
+            }
+            edits.push(Edit::DeleteComponent {
+                id,
+                component: g.component(id)?.clone(),
+            });
+            g.execute(Edit::Batch(edits))
+        })
     }
 
     /// Return the number of components in the network
     pub fn component_count(&self) -> usize {
-        self.components.borrow().len()
+        self.graph.read().unwrap().component_count
     }
 
     /// Print each component in the network
     pub fn list_components(&self) {
-        for component in self.components.borrow().iter() {
+        for component in self.components() {
             println!("<{}", component);
         }
     }
 
     /// Get a reference to the component with the given name, if it exists in the network
-    pub fn get_component(&self, name: &str) -> Result<Rc<dyn Component>, String> {
-        let components = self.components.borrow();
-        let component = components.iter().find(|x| x.name() == name);
-        match component {
-            Some(component) => Ok(component.clone()),
-            None => Err(format!(
-                "Component with name {} does not exist in network {}",
-                name,
-                self.name()
-            )),
-        }
+    pub fn get_component(&self, name: &str) -> Result<Arc<dyn Component>, Error> {
+        let g = self.graph.read().unwrap();
+        Ok(g.component(g.component_id(name)?)?.clone())
+    }
+
+    /// Get the id of the component with the given name if it exists
+    pub fn component_id(&self, name: &str) -> Result<ComponentId, Error> {
+        self.graph.read().unwrap().component_id(name)
     }
 
-    /// Connect a component terminal to a node. Returns an error if the component or node do not exist, or if the connection fails see [Component::connect]
+    /// Get a reference to the component with the given id if it exists
+    pub fn component(&self, id: ComponentId) -> Result<Arc<dyn Component>, Error> {
+        Ok(self.graph.read().unwrap().component(id)?.clone())
+    }
+
+    /// Return a reference to each component in the network
+    pub fn components(&self) -> Vec<Arc<dyn Component>> {
+        self.graph.read().unwrap().components().cloned().collect()
+    }
+
+    /// Return the id of each component in the network
+    pub fn component_ids(&self) -> Vec<ComponentId> {
+        let g = self.graph.read().unwrap();
+        (0..g.components.len())
+            .filter(|i| g.components[*i].is_some())
+            .map(ComponentId)
+            .collect()
+    }
+
+    /// Connect a component terminal to a node. Returns an error if the component or node do not exist, or if
+    /// * The component is already connected to the node on another terminal
+    /// * The given terminal index does not exist
+    /// * The given terminal is already connected to another node
     pub fn connect(
         &self,
         node_name: &str,
         component_name: &str,
         terminal: usize,
-    ) -> Result<(), String> {
-        let n = self.get_node(node_name);
-        let c = self.get_component(component_name);
-        match (n, c) {
-            (Err(_), Err(_)) => Err(format!(
-                "No node with name {} and component with name {} exist in network {}",
-                node_name,
-                component_name,
-                self.name()
-            )),
-            (Err(_), _) => Err(format!(
-                "No node with name {} exists in network {}",
-                node_name,
-                self.name()
-            )),
-            (_, Err(_)) => Err(format!(
-                "No component with name {} exists in network {}",
-                component_name,
-                self.name()
-            )),
-            (Ok(n), Ok(c)) => c.connect(n, terminal),
-        }
+    ) -> Result<(), Error> {
+        self.change(|g| {
+            let node = g.node_id(node_name)?;
+            let component = g.component_id(component_name)?;
+            g.execute(Edit::Connect {
+                node,
+                component,
+                terminal,
+            })
+        })
     }
 
     /// Disconnect a component from a node if it is connected. Returns an error if the component or node do not exist
-    pub fn disconnect(&self, node_name: &str, component_name: &str) -> Result<(), String> {
-        let n = self.get_node(node_name);
-        let c = self.get_component(component_name);
-        match (n, c) {
-            (Err(_), Err(_)) => Err(format!(
-                "No node with name {} and component with name {} exist in network {}",
-                node_name,
-                component_name,
-                self.name()
-            )),
-            (Err(_), _) => Err(format!(
-                "No node with name {} exists in network {}",
-                node_name,
-                self.name()
-            )),
-            (_, Err(_)) => Err(format!(
-                "No component with name {} exists in network {}",
-                component_name,
-                self.name()
-            )),
-            (Ok(n), Ok(c)) => c.disconnect(n),
+    pub fn disconnect(&self, node_name: &str, component_name: &str) -> Result<(), Error> {
+        self.change(|g| {
+            let node = g.node_id(node_name)?;
+            let component = g.component_id(component_name)?;
+            let terminal = g.terminal_of(node, component)?;
+            g.execute(Edit::Disconnect {
+                node,
+                component,
+                terminal,
+            })
+        })
+    }
+
+    /// Add an interlocking rule. Returns an error if the rule already exists, or if a component it refers to does not exist or is not switchgear.
+    pub fn add_interlock(&self, rule: Interlock) -> Result<(), Error> {
+        self.change(|g| {
+            let mut names = vec![rule.switch()];
+            if let Interlock::BreakerOpen { breaker, .. } = &rule {
+                names.push(breaker);
+            }
+            for name in names {
+                g.component(g.component_id(name)?)?.position()?;
+            }
+            g.execute(Edit::AddInterlock(rule))
+        })
+    }
+
+    /// Remove an interlocking rule. Returns an error if the rule does not exist.
+    pub fn remove_interlock(&self, rule: &Interlock) -> Result<(), Error> {
+        self.change(|g| g.execute(Edit::RemoveInterlock(rule.clone())))
+    }
+
+    /// Return the interlocking rules that guard the switch with the given name
+    pub fn interlocks(&self, switch_name: &str) -> Vec<Interlock> {
+        self.graph
+            .read()
+            .unwrap()
+            .interlocks
+            .iter()
+            .filter(|x| x.switch() == switch_name)
+            .cloned()
+            .collect()
+    }
+
+    /// Return all interlocking rules in the network
+    pub fn all_interlocks(&self) -> Vec<Interlock> {
+        self.graph.read().unwrap().interlocks.clone()
+    }
+
+    /// Open the switchgear with the given name, if no interlocking rule prevents it.
+    /// Switchgear with an operating time travels until the operation completes, see [Network::advance].
+    pub fn open(&self, name: &str) -> Result<(), Error> {
+        self.change(|g| {
+            let id = g.component_id(name)?;
+            g.check_operate(id, Operation::Open)?;
+            g.operate(id, Operation::Open)
+        })
+    }
+
+    /// Close the switchgear with the given name, if no interlocking rule prevents it and it does not short an energized node to earth, see [Network::check_earthing].
+    /// Switchgear with an operating time travels until the operation completes, see [Network::advance].
+    pub fn close(&self, name: &str) -> Result<(), Error> {
+        self.change(|g| {
+            let id = g.component_id(name)?;
+            g.check_operate(id, Operation::Close)?;
+            g.operate(id, Operation::Close)
+        })
+    }
+
+    /// Place a safety tag on the component with the given name; tagged switchgear can not be opened or closed.
+    /// A [TagKind::LockedOpen] tag can only be placed on open switchgear.
+    pub fn add_tag(&self, name: &str, tag: Tag) -> Result<(), Error> {
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            g.execute(Edit::AddTag { component, tag })
+        })
+    }
+
+    /// Remove the tag with the given tag name from the component with the given name
+    pub fn remove_tag(&self, name: &str, tag_name: &str) -> Result<(), Error> {
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            let tag = g
+                .component(component)?
+                .tags()
+                .read()
+                .unwrap()
+                .tags()
+                .iter()
+                .find(|t| t.name() == tag_name)
+                .cloned();
+            match tag {
+                Some(tag) => g.execute(Edit::RemoveTag { component, tag }),
+                None => Err(Error::NotFound {
+                    kind: "tag",
+                    name: tag_name.to_string(),
+                }),
+            }
+        })
+    }
+
+    /// Return the tags placed on the component with the given name
+    pub fn tags(&self, name: &str) -> Result<Vec<Tag>, Error> {
+        let c = self.get_component(name)?;
+        let tags = c.tags().read().unwrap().tags().clone();
+        Ok(tags)
+    }
+
+    /// Set the operating time of the circuit breaker or disconnector with the given name
+    pub fn set_operating_time(&self, name: &str, time: Duration) -> Result<(), Error> {
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            let c = g.component(component)?;
+            match c.r#type() {
+                ComponentType::CircuitBreaker | ComponentType::Disconnector => {}
+                t => {
+                    return Err(Error::InvalidArgument(format!(
+                        "{} {} has no operating time",
+                        t, name
+                    )))
+                }
+            }
+            let from = c.position()?.read().unwrap().operating_time();
+            g.execute(Edit::OperatingTime {
+                component,
+                from,
+                to: time,
+            })
+        })
+    }
+
+    /// Return the operating time of the switchgear with the given name
+    pub fn operating_time(&self, name: &str) -> Result<Duration, Error> {
+        let c = self.get_component(name)?;
+        let time = c.position()?.read().unwrap().operating_time();
+        Ok(time)
+    }
+
+    /// Return the simulation time; it starts at zero and moves forward with [Network::advance]
+    pub fn time(&self) -> Duration {
+        self.graph.read().unwrap().time
+    }
+
+    /// Return the switchgear operations in flight, in the order they were started
+    pub fn operations(&self) -> Vec<InFlight> {
+        self.graph.read().unwrap().in_flight.clone()
+    }
+
+    /// Move the simulation clock forward, completing the operations in flight that are due in the order they complete.
+    ///
+    /// The clock is recorded in the history, so undoing an advance also turns the clock back and the operations it completed are in flight again.
+    /// Interlocks and the earthing check are evaluated when an operation starts, see [Network::open] and [Network::close];
+    /// that check is binding and the operation completes regardless of what changed while it was travelling.
+    pub fn advance(&self, step: Duration) -> Result<(), Error> {
+        self.change(|g| {
+            let time = later(g.time, step)?;
+            let mut due: Vec<InFlight> = g
+                .in_flight
+                .iter()
+                .filter(|x| x.completes() <= time)
+                .copied()
+                .collect();
+            if step.is_zero() && due.is_empty() {
+                return Ok(());
+            }
+            due.sort_by_key(|x| x.completes());
+            let mut edits: Vec<Edit> = due
+                .into_iter()
+                .map(|operation| Edit::Finish {
+                    operation,
+                    complete: true,
+                })
+                .collect();
+            edits.push(Edit::Clock {
+                from: g.time,
+                to: time,
+            });
+            g.execute(Edit::Batch(edits))
+        })
+    }
+
+    /// Set the position of the switchgear with the given name as reported by telemetry, see [SwitchgearPosition::report](super::position::SwitchgearPosition::report).
+    /// Interlocks do not apply; the position already changed in the field.
+    pub fn report_position(&self, name: &str, position: SwitchPosition) -> Result<(), Error> {
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            let from = {
+                let pos = g.component(component)?.position()?.read().unwrap();
+                pos.check_report(position)?;
+                pos.position()
+            };
+            if from == position {
+                return Ok(());
+            }
+            g.execute(Edit::Position {
+                component,
+                from,
+                to: position,
+            })
+        })
+    }
+
+    /// Set an electrical parameter of the component with the given name, see [Component::parameters]
+    pub fn set_parameter(&self, name: &str, parameter: &str, value: f64) -> Result<(), Error> {
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            let c = g.component(component)?;
+            let from = c.parameter(parameter)?;
+            let parameter = match c.parameters().iter().find(|(p, _)| *p == parameter) {
+                Some((p, _)) => *p,
+                None => {
+                    return Err(Error::NotFound {
+                        kind: "parameter",
+                        name: parameter.to_string(),
+                    })
+                }
+            };
+            g.execute(Edit::Parameter {
+                component,
+                name: parameter,
+                from,
+                to: value,
+            })
+        })
+    }
+
+    /// Raise the tap position of the transformer with the given name, if it is not tagged
+    pub fn raise_tap(&self, name: &str) -> Result<(), Error> {
+        self.change(|g| g.tap(name, true))
+    }
+
+    /// Lower the tap position of the transformer with the given name, if it is not tagged
+    pub fn lower_tap(&self, name: &str) -> Result<(), Error> {
+        self.change(|g| g.tap(name, false))
+    }
+
+    /// Return the tap position of the transformer with the given name
+    pub fn tap_position(&self, name: &str) -> Result<i32, Error> {
+        Ok(self
+            .get_component(name)?
+            .transformer_parameters()?
+            .read()
+            .unwrap()
+            .tap_changer()
+            .position())
+    }
+
+    /// Set the [VectorGroup] of the transformer with the given name, e.g. Dyn11;
+    /// errors if its number of windings differs from the transformer
+    pub fn set_vector_group(&self, name: &str, group: &str) -> Result<(), Error> {
+        let group = VectorGroup::parse(group)?;
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            let from = g
+                .component(component)?
+                .transformer_parameters()?
+                .read()
+                .unwrap()
+                .vector_group()
+                .clone();
+            if from == group {
+                return Ok(());
+            }
+            g.execute(Edit::VectorGroup {
+                component,
+                from,
+                to: group,
+            })
+        })
+    }
+
+    /// Set the [LoadModel] of the load with the given name
+    pub fn set_load_model(&self, name: &str, model: LoadModel) -> Result<(), Error> {
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            let from = g
+                .component(component)?
+                .load_parameters()?
+                .read()
+                .unwrap()
+                .model();
+            if from == model {
+                return Ok(());
+            }
+            g.execute(Edit::LoadModel {
+                component,
+                from,
+                to: model,
+            })
+        })
+    }
+
+    /// Update the measurement value of the component with the given name
+    pub fn update(&self, name: &str, value: f64) -> Result<(), Error> {
+        self.change(|g| {
+            let component = g.component_id(name)?;
+            let from = g.component(component)?.value()?;
+            g.execute(Edit::Update {
+                component,
+                from,
+                to: value,
+            })
+        })
+    }
+
+    /// Revert the last change made through the network API. Returns an error if there is nothing to undo.
+    pub fn undo(&self) -> Result<(), Error> {
+        self.change(|g| match g.history.pop_undo() {
+            Some(edit) => match g.apply(&edit.inverse()) {
+                Ok(()) => {
+                    g.history.push_redo(edit);
+                    Ok(())
+                }
+                Err(e) => {
+                    g.history.push_undo(edit);
+                    Err(e)
+                }
+            },
+            None => Err(Error::NothingToUndo),
+        })
+    }
+
+    /// Reapply the last change reverted by [Network::undo]. Returns an error if there is nothing to redo.
+    pub fn redo(&self) -> Result<(), Error> {
+        self.change(|g| match g.history.pop_redo() {
+            Some(edit) => match g.apply(&edit) {
+                Ok(()) => {
+                    g.history.push_undo(edit);
+                    Ok(())
+                }
+                Err(e) => {
+                    g.history.push_redo(edit);
+                    Err(e)
+                }
+            },
+            None => Err(Error::NothingToRedo),
+        })
+    }
+
+    /// Set the [EventSource] recorded in the journal for subsequent changes, [EventSource::Api] by default
+    pub fn set_event_source(&self, source: EventSource) {
+        self.graph.write().unwrap().source = source;
+    }
+
+    /// Return a copy of the journal of all changes made through the network API
+    pub fn journal(&self) -> Journal {
+        self.graph.read().unwrap().journal.clone()
+    }
+
+    /// Forget all changes, so they can no longer be undone or redone
+    pub fn clear_history(&self) {
+        self.graph.write().unwrap().history.clear();
+    }
+
+    /// Forget all journaled events
+    pub fn clear_journal(&self) {
+        self.graph.write().unwrap().journal = Journal::new();
+    }
+
+    /// Call `f` with the component name, old and new position whenever a switchgear opens or closes
+    pub fn on_position_change<F>(&self, f: F) -> Subscription
+    where
+        F: Fn(&str, SwitchPosition, SwitchPosition) + Send + Sync + 'static,
+    {
+        self.observers.write().unwrap().add_position(Arc::new(f))
+    }
+
+    /// Call `f` with the component name, old and new value whenever a measurement is updated
+    pub fn on_measurement_update<F>(&self, f: F) -> Subscription
+    where
+        F: Fn(&str, f64, f64) + Send + Sync + 'static,
+    {
+        self.observers.write().unwrap().add_measurement(Arc::new(f))
+    }
+
+    /// Call `f` with the node or component name and the [TopologyChange] whenever the connections of the network change
+    pub fn on_topology_change<F>(&self, f: F) -> Subscription
+    where
+        F: Fn(&str, &TopologyChange) + Send + Sync + 'static,
+    {
+        self.observers.write().unwrap().add_topology(Arc::new(f))
+    }
+
+    /// Remove a callback registered with one of the `on_..` methods. Returns an error if it was already removed.
+    pub fn unsubscribe(&self, subscription: Subscription) -> Result<(), Error> {
+        if self.observers.write().unwrap().remove(subscription) {
+            Ok(())
+        } else {
+            Err(Error::NotFound {
+                kind: "subscription",
+                name: format!("{:?}", subscription),
+            })
         }
     }
+
+    /// Return the names of the neighbours of the node or component with the given name.
+    /// The neighbours of a node are the components connected to it, the neighbours of a component are the nodes connected to its terminals.
+    pub fn neighbours(&self, name: &str) -> Result<Vec<String>, Error> {
+        let g = self.graph.read().unwrap();
+        match g.names.get(name) {
+            Some(Element::Node(id)) => Ok(g.adjacency[id.0]
+                .iter()
+                .filter_map(|c| g.component(*c).ok())
+                .map(|c| c.name().clone())
+                .collect()),
+            Some(Element::Component(id)) => Ok(g
+                .component(*id)?
+                .nodes()
+                .iter()
+                .filter_map(|n| g.node(*n).ok())
+                .map(|n| n.name().clone())
+                .collect()),
+            None => Err(Error::NotFound {
+                kind: "node or component",
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+/// Check that a rated voltage is a positive number
+fn check_rated_voltage(rated_voltage: f64) -> Result<(), Error> {
+    if rated_voltage.is_finite() && rated_voltage > 0.0 {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument(
+            "Rated voltage has to be a positive number".to_string(),
+        ))
+    }
+}
+
+/// Move a simulation time forward; errors instead of overflowing the clock
+fn later(time: Duration, step: Duration) -> Result<Duration, Error> {
+    match time.checked_add(step) {
+        Some(time) => Ok(time),
+        None => Err(Error::InvalidArgument(
+            "Simulation time out of range".to_string(),
+        )),
+    }
 }
 
 impl fmt::Display for Network {
@@ -235,7 +1702,7 @@ mod tests {
         assert!(net.get_component("cb").is_err());
         net.create_component::<CircuitBreaker>("cb").unwrap();
         assert!(net.get_component("cb").unwrap().name() == "cb");
-        assert!(net.create_node("cb").is_err());
+        assert!(matches!(net.create_node("cb"), Err(Error::NameInUse(_))));
         assert!(net.create_component::<Disconnector>("cb").is_err());
         assert_eq!(net.component_count(), 1);
 
@@ -243,12 +1710,12 @@ mod tests {
         assert!(net.get_component("ds").unwrap().name() == "ds");
         assert_eq!(net.component_count(), 2);
 
-        net.remove_component("cb").unwrap();
+        net.remove_component("cb", RemoveMode::Cascade).unwrap();
         assert!(net.get_component("cb").is_err());
         assert_eq!(net.component_count(), 1);
-        assert!(net.remove_component("cb").is_err());
+        assert!(net.remove_component("cb", RemoveMode::Cascade).is_err());
 
-        net.remove_component("ds").unwrap();
+        net.remove_component("ds", RemoveMode::Cascade).unwrap();
         assert_eq!(net.component_count(), 0);
     }
 
@@ -268,13 +1735,130 @@ mod tests {
         assert!(net.get_node("node2").unwrap().name() == "node2");
         assert_eq!(net.node_count(), 2);
 
-        net.remove_node("node").unwrap();
+        net.remove_node("node", RemoveMode::Cascade).unwrap();
         assert!(net.get_node("node").is_err());
-        assert!(net.remove_node("node").is_err());
+        assert!(net.remove_node("node", RemoveMode::Cascade).is_err());
         assert_eq!(net.node_count(), 1);
 
-        net.remove_node("node2").unwrap();
+        net.remove_node("node2", RemoveMode::Cascade).unwrap();
+        assert_eq!(net.node_count(), 0);
+    }
+
+    #[test]
+    fn network_ids() {
+        let net = Network::new("net");
+        let n = net.create_node("node").unwrap();
+        let n2 = net.create_node("node2").unwrap();
+        let cb = net.create_component::<CircuitBreaker>("cb").unwrap();
+
+        assert_eq!(net.node_id("node").unwrap(), n);
+        assert_eq!(net.component_id("cb").unwrap(), cb);
+        assert!(net.node_id("cb").is_err());
+        assert!(net.component_id("node").is_err());
+        assert_eq!(net.node(n2).unwrap().name(), "node2");
+        assert_eq!(net.component(cb).unwrap().name(), "cb");
+
+        net.connect("node2", "cb", 1).unwrap();
+        assert_eq!(net.node_components(n2).unwrap(), vec![cb]);
+        assert_eq!(net.component(cb).unwrap().nodes(), vec![n2]);
+
+        net.remove_node("node", RemoveMode::Cascade).unwrap();
+        assert!(net.node(n).is_err());
+        assert!(net.node_components(n).is_err());
+        assert_eq!(net.node_ids(), vec![n2]);
+        assert_eq!(net.component_ids(), vec![cb]);
+
+        let n3 = net.create_node("node").unwrap();
+        assert_ne!(n3, n);
+    }
+
+    #[test]
+    fn network_undo() {
+        let net = Network::new("net");
+        assert!(matches!(net.undo(), Err(Error::NothingToUndo)));
+        let n = net.create_node("node").unwrap();
+        let cb = net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.connect("node", "cb", 1).unwrap();
+        net.connect("node", "vt", 0).unwrap();
+        net.add_source("node").unwrap();
+        net.close("cb").unwrap();
+        net.update("vt", 10.0).unwrap();
+        net.update("vt", 20.0).unwrap();
+
+        net.remove_node("node", RemoveMode::Cascade).unwrap();
+        assert!(net.get_node("node").is_err());
+        net.undo().unwrap();
+        assert_eq!(net.node_id("node").unwrap(), n);
+        assert_eq!(net.node_components(n).unwrap().len(), 2);
+        assert_eq!(net.component(cb).unwrap().nodes(), vec![n]);
+        assert_eq!(net.source_ids(), vec![n]);
+
+        net.undo().unwrap();
+        assert_eq!(net.get_component("vt").unwrap().value().unwrap(), 10.0);
+        net.undo().unwrap();
+        net.undo().unwrap();
+        assert!(net
+            .component(cb)
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_open());
+        net.redo().unwrap();
+        assert!(net
+            .component(cb)
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_closed());
+
+        // A failed undo or redo keeps the edit, so it can be retried
+        net.component(cb).unwrap().open().unwrap();
+        assert!(matches!(net.undo(), Err(Error::AlreadyOpen)));
+        net.component(cb).unwrap().close().unwrap();
+        net.undo().unwrap();
+        net.component(cb).unwrap().close().unwrap();
+        assert!(matches!(net.redo(), Err(Error::AlreadyClosed)));
+        net.component(cb).unwrap().open().unwrap();
+        net.redo().unwrap();
+
+        for _ in 0..7 {
+            net.undo().unwrap();
+        }
         assert_eq!(net.node_count(), 0);
+        assert_eq!(net.component_count(), 0);
+        assert!(net.undo().is_err());
+        net.redo().unwrap();
+        assert_eq!(net.node_id("node").unwrap(), n);
+
+        net.create_node("node2").unwrap();
+        assert!(matches!(net.redo(), Err(Error::NothingToRedo)));
+    }
+
+    #[test]
+    fn network_batch_rollback() {
+        let net = Network::new("net");
+        let n = net.create_node("node").unwrap();
+        let cb = net.create_component::<CircuitBreaker>("cb").unwrap();
+        let events = net.journal().events().len();
+        let connect = |terminal| Edit::Connect {
+            node: n,
+            component: cb,
+            terminal,
+        };
+        let result = net.change(|g| g.execute(Edit::Batch(vec![connect(0), connect(1)])));
+        assert!(matches!(result, Err(Error::AlreadyConnected { .. })));
+        assert!(net.node_components(n).unwrap().is_empty());
+        assert!(net.component(cb).unwrap().nodes().is_empty());
+        assert_eq!(net.journal().events().len(), events);
+        assert!(net.graph().pending.is_empty());
+
+        net.undo().unwrap();
+        assert_eq!(net.component_count(), 0);
     }
 
     #[test]
@@ -283,12 +1867,377 @@ mod tests {
         net.create_node("node").unwrap();
         net.create_component::<CircuitBreaker>("cb").unwrap();
 
-        assert!(net.connect("does not", "exist", 0).is_err());
+        assert!(matches!(
+            net.connect("does not", "exist", 0),
+            Err(Error::NotFound { kind: "node", .. })
+        ));
+        assert!(matches!(
+            net.connect("node", "exist", 0),
+            Err(Error::NotFound {
+                kind: "component",
+                ..
+            })
+        ));
         assert!(net.disconnect("node", "cb").is_err());
         assert!(net.connect("node", "cb", 2).is_err());
         assert!(net.connect("node", "cb", 0).is_ok());
         assert!(net.connect("node", "cb", 1).is_err());
         assert!(net.disconnect("node", "cb").is_ok());
         assert!(net.connect("node", "cb", 1).is_ok());
+
+        net.create_node("node2").unwrap();
+        assert!(matches!(
+            net.connect("node2", "cb", 1),
+            Err(Error::TerminalConnected)
+        ));
+    }
+
+    #[test]
+    fn network_remove_connected() {
+        let net = Network::new("net");
+        net.create_node("node").unwrap();
+        net.create_node("node2").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.connect("node", "cb", 0).unwrap();
+        net.connect("node2", "cb", 1).unwrap();
+        net.connect("node", "vt", 0).unwrap();
+
+        assert!(net
+            .remove_node("node", RemoveMode::RefuseIfConnected)
+            .is_err());
+        assert!(net
+            .remove_component("cb", RemoveMode::RefuseIfConnected)
+            .is_err());
+        assert_eq!(net.node_count(), 2);
+        assert_eq!(net.component_count(), 2);
+
+        let cb = net.get_component("cb").unwrap();
+        net.remove_node("node", RemoveMode::Cascade).unwrap();
+        assert!(cb.terminal(0).unwrap().read().unwrap().get_node().is_err());
+        assert!(net.get_component("vt").unwrap().nodes().is_empty());
+        assert!(net
+            .remove_component("vt", RemoveMode::RefuseIfConnected)
+            .is_ok());
+
+        let node2 = net.node_id("node2").unwrap();
+        net.remove_component("cb", RemoveMode::Cascade).unwrap();
+        assert!(net.node_components(node2).unwrap().is_empty());
+        assert!(cb.nodes().is_empty());
+        assert!(net
+            .remove_node("node2", RemoveMode::RefuseIfConnected)
+            .is_ok());
+    }
+
+    #[test]
+    fn network_threads() {
+        let net = Arc::new(Network::new("net"));
+        net.create_node("node").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.connect("node", "vt", 0).unwrap();
+        net.connect("node", "cb", 0).unwrap();
+        let updates = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        {
+            let updates = updates.clone();
+            net.on_measurement_update(move |_, _, _| {
+                updates.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            });
+        }
+
+        let telemetry = {
+            let net = net.clone();
+            std::thread::spawn(move || {
+                for i in 1..=100 {
+                    net.update("vt", i as f64).unwrap();
+                }
+            })
+        };
+        for _ in 0..100 {
+            assert_eq!(net.topology().buses().len(), 1);
+        }
+        telemetry.join().unwrap();
+        assert_eq!(net.get_component("vt").unwrap().value().unwrap(), 100.0);
+        assert_eq!(updates.load(std::sync::atomic::Ordering::SeqCst), 100);
+        assert_eq!(net.journal().for_name("vt").events().len(), 102);
+    }
+
+    #[test]
+    fn network_neighbours() {
+        let net = Network::new("net");
+        net.create_node("node").unwrap();
+        net.create_node("node2").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.connect("node", "cb", 0).unwrap();
+        net.connect("node2", "cb", 1).unwrap();
+        net.connect("node", "vt", 0).unwrap();
+
+        assert!(net.neighbours("does not exist").is_err());
+        assert_eq!(net.neighbours("node").unwrap(), vec!["cb", "vt"]);
+        assert_eq!(net.neighbours("cb").unwrap(), vec!["node", "node2"]);
+        net.disconnect("node", "cb").unwrap();
+        assert_eq!(net.neighbours("node").unwrap(), vec!["vt"]);
+        assert_eq!(net.neighbours("cb").unwrap(), vec!["node2"]);
+    }
+
+    #[test]
+    fn network_source() {
+        let net = Network::new("net");
+        net.create_node("node").unwrap();
+
+        assert!(net.add_source("does not exist").is_err());
+        assert!(net.remove_source("node").is_err());
+        net.add_source("node").unwrap();
+        assert!(net.add_source("node").is_err());
+        assert_eq!(net.sources().len(), 1);
+        net.remove_source("node").unwrap();
+        assert!(net.sources().is_empty());
+
+        net.add_source("node").unwrap();
+        net.remove_node("node", RemoveMode::Cascade).unwrap();
+        assert!(net.sources().is_empty());
+    }
+
+    #[test]
+    fn network_report_position() {
+        let net = Network::new("net");
+        net.create_node("node").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<EarthingSwitch>("es").unwrap();
+        net.connect("node", "cb", 0).unwrap();
+        net.connect("node", "es", 0).unwrap();
+        net.add_interlock(Interlock::BreakerOpen {
+            switch: "es".to_string(),
+            breaker: "cb".to_string(),
+        })
+        .unwrap();
+        let position = |name: &str| {
+            net.get_component(name)
+                .unwrap()
+                .position()
+                .unwrap()
+                .read()
+                .unwrap()
+                .position()
+        };
+
+        // Telemetry is not subject to interlocks
+        net.close("cb").unwrap();
+        net.report_position("es", SwitchPosition::Intermediate)
+            .unwrap();
+        assert!(matches!(
+            net.close("es"),
+            Err(Error::InterlockViolation { .. })
+        ));
+        net.open("cb").unwrap();
+        assert!(matches!(
+            net.close("es"),
+            Err(Error::InvalidTransition { .. })
+        ));
+        net.report_position("es", SwitchPosition::Bad).unwrap();
+        assert!(net
+            .report_position("es", SwitchPosition::Intermediate)
+            .is_err());
+        net.report_position("es", SwitchPosition::Bad).unwrap();
+        assert!(net.report_position("vt", SwitchPosition::Open).is_err());
+
+        let j = net.journal().for_name("es");
+        assert_eq!(j.events().len(), 5);
+        assert_eq!(j.events()[4].description(), "Reported Bad");
+
+        net.undo().unwrap();
+        assert_eq!(position("es"), SwitchPosition::Intermediate);
+        net.undo().unwrap();
+        net.undo().unwrap();
+        assert_eq!(position("es"), SwitchPosition::Open);
+        net.redo().unwrap();
+        assert_eq!(position("es"), SwitchPosition::Intermediate);
+    }
+
+    #[test]
+    fn network_tags() {
+        let net = Network::new("net");
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.set_operating_time("ds", Duration::from_secs(1))
+            .unwrap();
+
+        net.add_tag("cb", Tag::new("T1", TagKind::DoNotOperate))
+            .unwrap();
+        net.add_tag("ds", Tag::new("T1", TagKind::LockedOpen))
+            .unwrap();
+        assert!(net
+            .add_tag("cb", Tag::new("T1", TagKind::OutOfService))
+            .is_err());
+        assert!(net
+            .add_tag("does not exist", Tag::new("T2", TagKind::OutOfService))
+            .is_err());
+        assert!(matches!(net.close("cb"), Err(Error::Tagged { .. })));
+        assert!(matches!(net.close("ds"), Err(Error::Tagged { .. })));
+        assert!(net.operations().is_empty());
+        assert_eq!(net.tags("cb").unwrap()[0].kind(), TagKind::DoNotOperate);
+
+        // Only open switchgear can be locked open
+        net.create_component::<Disconnector>("ds2").unwrap();
+        net.create_component::<Line>("line").unwrap();
+        net.close("ds2").unwrap();
+        assert!(matches!(
+            net.add_tag("ds2", Tag::new("T1", TagKind::LockedOpen)),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(net
+            .add_tag("line", Tag::new("T1", TagKind::LockedOpen))
+            .is_err());
+        assert!(net.tags("ds2").unwrap().is_empty());
+        net.open("ds2").unwrap();
+        net.add_tag("ds2", Tag::new("T1", TagKind::LockedOpen))
+            .unwrap();
+
+        assert!(net.remove_tag("cb", "T2").is_err());
+        net.remove_tag("cb", "T1").unwrap();
+        net.close("cb").unwrap();
+        assert_eq!(
+            net.journal().for_name("cb").events()[2].description(),
+            "Removed tag Do Not Operate T1"
+        );
+
+        // Undoing the close and the tag removal puts the tag back
+        net.undo().unwrap();
+        net.undo().unwrap();
+        assert!(net.close("cb").is_err());
+    }
+
+    #[test]
+    fn network_parameters() {
+        let net = Network::new("net");
+        net.create_component::<Line>("line").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+
+        net.set_parameter("line", "length", 12.0).unwrap();
+        assert!(net.set_parameter("line", "length", -12.0).is_err());
+        assert!(net.set_parameter("line", "ratio", 1.0).is_err());
+        assert!(net.set_parameter("cb", "length", 1.0).is_err());
+        assert_eq!(
+            net.get_component("line")
+                .unwrap()
+                .parameter("length")
+                .unwrap(),
+            12.0
+        );
+        assert_eq!(
+            net.journal().for_name("line").events()[1].description(),
+            "Changed length from 0 to 12"
+        );
+
+        net.undo().unwrap();
+        assert_eq!(
+            net.get_component("line")
+                .unwrap()
+                .parameter("length")
+                .unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn network_load_model() {
+        let net = Network::new("net");
+        net.create_component::<Load>("load").unwrap();
+        net.create_component::<Generator>("gen").unwrap();
+        let model = || {
+            net.get_component("load")
+                .unwrap()
+                .load_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .model()
+        };
+
+        net.set_load_model("load", LoadModel::ConstantImpedance)
+            .unwrap();
+        assert_eq!(model(), LoadModel::ConstantImpedance);
+        assert!(matches!(
+            net.set_load_model("gen", LoadModel::ConstantPower),
+            Err(Error::NoLoadParameters(_))
+        ));
+        assert_eq!(
+            net.journal().for_name("load").events()[1].description(),
+            "Load model set to Constant Impedance"
+        );
+
+        net.undo().unwrap();
+        assert_eq!(model(), LoadModel::ConstantPower);
+    }
+
+    #[test]
+    fn network_vector_group() {
+        let net = Network::new("net");
+        net.create_component::<Transformer>("tf").unwrap();
+        net.create_component::<ThreeWindingTransformer>("tf3")
+            .unwrap();
+        net.create_component::<Load>("load").unwrap();
+        let group = |name: &str| {
+            net.get_component(name)
+                .unwrap()
+                .transformer_parameters()
+                .unwrap()
+                .read()
+                .unwrap()
+                .vector_group()
+                .to_string()
+        };
+
+        net.set_vector_group("tf", "Dyn11").unwrap();
+        net.set_vector_group("tf3", "YNyn0d5").unwrap();
+        assert_eq!(group("tf"), "Dyn11");
+        assert!(net.set_vector_group("tf", "YNyn0d5").is_err());
+        assert!(net.set_vector_group("tf", "Dyn13").is_err());
+        assert!(matches!(
+            net.set_vector_group("load", "Dyn11"),
+            Err(Error::NoTransformerParameters(_))
+        ));
+        assert_eq!(
+            net.journal().for_name("tf").events()[1].description(),
+            "Vector group set to Dyn11"
+        );
+
+        net.undo().unwrap();
+        net.undo().unwrap();
+        assert_eq!(group("tf"), "YNyn0");
+        assert_eq!(group("tf3"), "YNyn0d11");
+    }
+
+    #[test]
+    fn network_tap_changer() {
+        let net = Network::new("net");
+        net.create_component::<Transformer>("tf").unwrap();
+        net.create_component::<Line>("line").unwrap();
+        net.set_parameter("tf", "tap_min", -1.0).unwrap();
+        net.set_parameter("tf", "tap_max", 2.0).unwrap();
+
+        net.raise_tap("tf").unwrap();
+        net.raise_tap("tf").unwrap();
+        assert!(matches!(net.raise_tap("tf"), Err(Error::TapLimit(2))));
+        assert_eq!(net.tap_position("tf").unwrap(), 2);
+        assert!(net.set_parameter("tf", "tap_max", 1.0).is_err());
+        assert!(net.raise_tap("line").is_err());
+        assert!(net.tap_position("line").is_err());
+
+        net.add_tag("tf", Tag::new("T1", TagKind::DoNotOperate))
+            .unwrap();
+        assert!(matches!(net.lower_tap("tf"), Err(Error::Tagged { .. })));
+        net.remove_tag("tf", "T1").unwrap();
+        net.lower_tap("tf").unwrap();
+        assert_eq!(net.tap_position("tf").unwrap(), 1);
+
+        let j = net.journal().for_name("tf");
+        let descriptions: Vec<&String> = j.events().iter().map(|e| e.description()).collect();
+        assert_eq!(descriptions[3], "Tap raised to 1");
+        assert_eq!(descriptions[7], "Tap lowered to 1");
+
+        net.undo().unwrap();
+        assert_eq!(net.tap_position("tf").unwrap(), 2);
     }
 }
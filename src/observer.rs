@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use super::position::SwitchPosition;
+
+/// Change to the connections of a network, passed to [Network::on_topology_change](super::network::Network::on_topology_change)
+#[derive(Debug, PartialEq, Clone)]
+pub enum TopologyChange {
+    /// The node was created
+    NodeCreated,
+    /// The node was removed
+    NodeRemoved,
+    /// The component was created
+    ComponentCreated,
+    /// The component was removed
+    ComponentRemoved,
+    /// A component terminal was connected to the node with the given name
+    Connected {
+        /// Terminal index
+        terminal: usize,
+        /// Node name
+        node: String,
+    },
+    /// A component terminal was disconnected from the node with the given name
+    Disconnected {
+        /// Terminal index
+        terminal: usize,
+        /// Node name
+        node: String,
+    },
+}
+
+/// Handle of a registered callback, used to unsubscribe it with [Network::unsubscribe](super::network::Network::unsubscribe)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Subscription(usize);
+
+type PositionCallback = Arc<dyn Fn(&str, SwitchPosition, SwitchPosition) + Send + Sync>;
+type MeasurementCallback = Arc<dyn Fn(&str, f64, f64) + Send + Sync>;
+type TopologyCallback = Arc<dyn Fn(&str, &TopologyChange) + Send + Sync>;
+
+/// A change to report to the observers, queued while the network is locked
+pub(crate) enum Notification {
+    Position {
+        name: String,
+        old: SwitchPosition,
+        new: SwitchPosition,
+    },
+    Measurement {
+        name: String,
+        old: f64,
+        new: f64,
+    },
+    Topology {
+        name: String,
+        change: TopologyChange,
+    },
+}
+
+/// Callbacks registered on a network. Cloning only copies the references to the callbacks,
+/// so they can be called without holding a lock on the network.
+#[derive(Default, Clone)]
+pub(crate) struct Observers {
+    next: usize,
+    position: Vec<(Subscription, PositionCallback)>,
+    measurement: Vec<(Subscription, MeasurementCallback)>,
+    topology: Vec<(Subscription, TopologyCallback)>,
+}
+
+impl Observers {
+    fn subscription(&mut self) -> Subscription {
+        self.next += 1;
+        Subscription(self.next)
+    }
+
+    pub(crate) fn add_position(&mut self, f: PositionCallback) -> Subscription {
+        let s = self.subscription();
+        self.position.push((s, f));
+        s
+    }
+
+    pub(crate) fn add_measurement(&mut self, f: MeasurementCallback) -> Subscription {
+        let s = self.subscription();
+        self.measurement.push((s, f));
+        s
+    }
+
+    pub(crate) fn add_topology(&mut self, f: TopologyCallback) -> Subscription {
+        let s = self.subscription();
+        self.topology.push((s, f));
+        s
+    }
+
+    /// Remove the callback with the given subscription; returns false if there is none
+    pub(crate) fn remove(&mut self, s: Subscription) -> bool {
+        let count = self.position.len() + self.measurement.len() + self.topology.len();
+        self.position.retain(|(x, _)| *x != s);
+        self.measurement.retain(|(x, _)| *x != s);
+        self.topology.retain(|(x, _)| *x != s);
+        count != self.position.len() + self.measurement.len() + self.topology.len()
+    }
+
+    /// Call each callback that observes the notification
+    pub(crate) fn notify(&self, n: &Notification) {
+        match n {
+            Notification::Position { name, old, new } => {
+                for (_, f) in &self.position {
+                    f(name, *old, *new);
+                }
+            }
+            Notification::Measurement { name, old, new } => {
+                for (_, f) in &self.measurement {
+                    f(name, *old, *new);
+                }
+            }
+            Notification::Topology { name, change } => {
+                for (_, f) in &self.topology {
+                    f(name, change);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::*;
+    use crate::network::{Network, RemoveMode};
+    use std::sync::Mutex;
+
+    #[test]
+    fn observer_callbacks() {
+        let net = Arc::new(Network::new("net"));
+        let log = Arc::new(Mutex::new(vec![]));
+
+        let l = log.clone();
+        let n = net.clone();
+        let position = net.on_position_change(move |name, old, new| {
+            // Callbacks run without a lock on the network, so they can query it
+            let closed = n
+                .get_component(name)
+                .unwrap()
+                .position()
+                .unwrap()
+                .read()
+                .unwrap()
+                .is_closed();
+            l.lock()
+                .unwrap()
+                .push(format!("{} {} -> {} {}", name, old, new, closed));
+        });
+        let l = log.clone();
+        net.on_measurement_update(move |name, old, new| {
+            l.lock()
+                .unwrap()
+                .push(format!("{} {} -> {}", name, old, new));
+        });
+        let l = log.clone();
+        net.on_topology_change(move |name, change| {
+            l.lock().unwrap().push(format!("{} {:?}", name, change));
+        });
+
+        net.create_node("node").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.connect("node", "cb", 1).unwrap();
+        net.close("cb").unwrap();
+        net.update("vt", 2.5).unwrap();
+        net.undo().unwrap();
+        net.unsubscribe(position).unwrap();
+        assert!(net.unsubscribe(position).is_err());
+        net.open("cb").unwrap();
+        net.remove_node("node", RemoveMode::Cascade).unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "node NodeCreated",
+                "cb ComponentCreated",
+                "vt ComponentCreated",
+                "cb Connected { terminal: 1, node: \"node\" }",
+                "cb Open -> Closed true",
+                "vt 0 -> 2.5",
+                "vt 2.5 -> 0",
+                "cb Disconnected { terminal: 1, node: \"node\" }",
+                "node NodeRemoved",
+            ]
+        );
+    }
+}
@@ -0,0 +1,874 @@
+use std::fmt;
+
+use super::error::Error;
+use super::tap::TapChanger;
+
+/// Electrical parameters of a line or cable
+///
+/// Series resistance and reactance are in ohm per km, shunt susceptance in siemens per km,
+/// the length in km and the thermal rating in ampere.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct LineParameters {
+    r: f64,
+    x: f64,
+    b: f64,
+    length: f64,
+    rating: f64,
+}
+
+impl LineParameters {
+    /// Names of the parameters, as used by [LineParameters::get] and [LineParameters::set]
+    pub const NAMES: [&'static str; 5] = ["r", "x", "b", "length", "rating"];
+
+    /// Constructor; errors if a parameter is negative or not finite
+    pub fn new(r: f64, x: f64, b: f64, length: f64, rating: f64) -> Result<LineParameters, Error> {
+        let mut p = LineParameters::default();
+        for (name, value) in LineParameters::NAMES.iter().zip([r, x, b, length, rating]) {
+            p.set(name, value)?;
+        }
+        Ok(p)
+    }
+
+    /// Return the series resistance per km
+    pub fn r(&self) -> f64 {
+        self.r
+    }
+
+    /// Return the series reactance per km
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// Return the shunt susceptance per km
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    /// Return the length in km
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+
+    /// Return the thermal rating in ampere
+    pub fn rating(&self) -> f64 {
+        self.rating
+    }
+
+    /// Return the series resistance and reactance of the whole length
+    pub fn impedance(&self) -> (f64, f64) {
+        (self.r * self.length, self.x * self.length)
+    }
+
+    /// Return the shunt susceptance of the whole length
+    pub fn susceptance(&self) -> f64 {
+        self.b * self.length
+    }
+
+    /// Get a parameter by name
+    pub fn get(&self, name: &str) -> Result<f64, Error> {
+        match name {
+            "r" => Ok(self.r),
+            "x" => Ok(self.x),
+            "b" => Ok(self.b),
+            "length" => Ok(self.length),
+            "rating" => Ok(self.rating),
+            _ => Err(Error::NotFound {
+                kind: "parameter",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Set a parameter by name; errors if the value is negative or not finite
+    pub fn set(&mut self, name: &str, value: f64) -> Result<(), Error> {
+        let p = match name {
+            "r" => &mut self.r,
+            "x" => &mut self.x,
+            "b" => &mut self.b,
+            "length" => &mut self.length,
+            "rating" => &mut self.rating,
+            _ => {
+                return Err(Error::NotFound {
+                    kind: "parameter",
+                    name: name.to_string(),
+                })
+            }
+        };
+        if !value.is_finite() || value < 0.0 {
+            return Err(Error::InvalidArgument(format!(
+                "Parameter {} has to be a positive number",
+                name
+            )));
+        }
+        *p = value;
+        Ok(())
+    }
+}
+
+/// Voltage dependency of the demand of a load
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LoadModel {
+    /// The demand does not depend on the voltage
+    ConstantPower,
+    /// The demand is proportional to the square of the voltage
+    ConstantImpedance,
+}
+
+impl fmt::Display for LoadModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let modelstr = match self {
+            LoadModel::ConstantPower => "Constant Power",
+            LoadModel::ConstantImpedance => "Constant Impedance",
+        };
+        write!(f, "{}", modelstr)
+    }
+}
+
+/// Demand of a load
+///
+/// Active power is in MW and reactive power in Mvar, both at nominal voltage.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LoadParameters {
+    p: f64,
+    q: f64,
+    model: LoadModel,
+}
+
+impl LoadParameters {
+    /// Names of the parameters, as used by [LoadParameters::get] and [LoadParameters::set]
+    pub const NAMES: [&'static str; 2] = ["p", "q"];
+
+    /// Constructor; errors if a parameter is not finite
+    pub fn new(p: f64, q: f64, model: LoadModel) -> Result<LoadParameters, Error> {
+        let mut l = LoadParameters::default();
+        l.set("p", p)?;
+        l.set("q", q)?;
+        l.set_model(model);
+        Ok(l)
+    }
+
+    /// Return the active power demand at nominal voltage
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Return the reactive power demand at nominal voltage
+    pub fn q(&self) -> f64 {
+        self.q
+    }
+
+    /// Return the [LoadModel]
+    pub fn model(&self) -> LoadModel {
+        self.model
+    }
+
+    /// Set the [LoadModel]
+    pub fn set_model(&mut self, model: LoadModel) {
+        self.model = model;
+    }
+
+    /// Return the active and reactive power demand at the given voltage in per unit
+    pub fn demand(&self, v: f64) -> (f64, f64) {
+        match self.model {
+            LoadModel::ConstantPower => (self.p, self.q),
+            LoadModel::ConstantImpedance => (self.p * v * v, self.q * v * v),
+        }
+    }
+
+    /// Get a parameter by name
+    pub fn get(&self, name: &str) -> Result<f64, Error> {
+        match name {
+            "p" => Ok(self.p),
+            "q" => Ok(self.q),
+            _ => Err(Error::NotFound {
+                kind: "parameter",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Set a parameter by name; errors if the value is not finite
+    pub fn set(&mut self, name: &str, value: f64) -> Result<(), Error> {
+        let p = match name {
+            "p" => &mut self.p,
+            "q" => &mut self.q,
+            _ => {
+                return Err(Error::NotFound {
+                    kind: "parameter",
+                    name: name.to_string(),
+                })
+            }
+        };
+        if !value.is_finite() {
+            return Err(Error::InvalidArgument(format!(
+                "Parameter {} has to be a number",
+                name
+            )));
+        }
+        *p = value;
+        Ok(())
+    }
+}
+
+impl Default for LoadParameters {
+    fn default() -> Self {
+        LoadParameters {
+            p: 0.0,
+            q: 0.0,
+            model: LoadModel::ConstantPower,
+        }
+    }
+}
+
+/// Setpoints and limits of a generator
+///
+/// Active power is in MW, the voltage setpoint in per unit and the reactive power limits in Mvar.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct GeneratorParameters {
+    p: f64,
+    v: f64,
+    q_min: f64,
+    q_max: f64,
+}
+
+impl GeneratorParameters {
+    /// Names of the parameters, as used by [GeneratorParameters::get] and [GeneratorParameters::set]
+    pub const NAMES: [&'static str; 4] = ["p", "v", "q_min", "q_max"];
+
+    /// Constructor; errors if a parameter is invalid, see [GeneratorParameters::set]
+    pub fn new(p: f64, v: f64, q_min: f64, q_max: f64) -> Result<GeneratorParameters, Error> {
+        let mut g = GeneratorParameters::default();
+        for (name, value) in GeneratorParameters::NAMES.iter().zip([p, v, q_min, q_max]) {
+            g.set(name, value)?;
+        }
+        Ok(g)
+    }
+
+    /// Return the active power setpoint
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Return the voltage setpoint
+    pub fn v(&self) -> f64 {
+        self.v
+    }
+
+    /// Return the lower reactive power limit
+    pub fn q_min(&self) -> f64 {
+        self.q_min
+    }
+
+    /// Return the upper reactive power limit
+    pub fn q_max(&self) -> f64 {
+        self.q_max
+    }
+
+    /// Return the given reactive power clamped to the limits
+    pub fn limit_q(&self, q: f64) -> f64 {
+        q.max(self.q_min).min(self.q_max)
+    }
+
+    /// Get a parameter by name
+    pub fn get(&self, name: &str) -> Result<f64, Error> {
+        match name {
+            "p" => Ok(self.p),
+            "v" => Ok(self.v),
+            "q_min" => Ok(self.q_min),
+            "q_max" => Ok(self.q_max),
+            _ => Err(Error::NotFound {
+                kind: "parameter",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Set a parameter by name; errors if the value is not finite, or the voltage setpoint is not positive
+    ///
+    /// The reactive power limits are not checked against each other, so they can be set in any order.
+    pub fn set(&mut self, name: &str, value: f64) -> Result<(), Error> {
+        let p = match name {
+            "p" => &mut self.p,
+            "v" => &mut self.v,
+            "q_min" => &mut self.q_min,
+            "q_max" => &mut self.q_max,
+            _ => {
+                return Err(Error::NotFound {
+                    kind: "parameter",
+                    name: name.to_string(),
+                })
+            }
+        };
+        if !value.is_finite() || (name == "v" && value <= 0.0) {
+            return Err(Error::InvalidArgument(format!(
+                "Parameter {} has to be a {}number",
+                name,
+                if name == "v" { "positive " } else { "" }
+            )));
+        }
+        *p = value;
+        Ok(())
+    }
+}
+
+impl Default for GeneratorParameters {
+    fn default() -> Self {
+        GeneratorParameters {
+            p: 0.0,
+            v: 1.0,
+            q_min: 0.0,
+            q_max: 0.0,
+        }
+    }
+}
+
+/// Equivalent of the grid beyond the network, the slack of a simulation
+///
+/// The short-circuit power is in MVA and the voltage setpoint in per unit;
+/// the R/X ratio sets the angle of the equivalent impedance.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExternalGridParameters {
+    sk: f64,
+    rx: f64,
+    v: f64,
+}
+
+impl ExternalGridParameters {
+    /// Names of the parameters, as used by [ExternalGridParameters::get] and [ExternalGridParameters::set]
+    pub const NAMES: [&'static str; 3] = ["sk", "rx", "v"];
+
+    /// Constructor; errors if a parameter is negative or not finite
+    pub fn new(sk: f64, rx: f64, v: f64) -> Result<ExternalGridParameters, Error> {
+        let mut e = ExternalGridParameters::default();
+        for (name, value) in ExternalGridParameters::NAMES.iter().zip([sk, rx, v]) {
+            e.set(name, value)?;
+        }
+        Ok(e)
+    }
+
+    /// Return the short-circuit power
+    pub fn sk(&self) -> f64 {
+        self.sk
+    }
+
+    /// Return the R/X ratio
+    pub fn rx(&self) -> f64 {
+        self.rx
+    }
+
+    /// Return the voltage setpoint
+    pub fn v(&self) -> f64 {
+        self.v
+    }
+
+    /// Return the resistance and reactance in ohm of the equivalent impedance at the given rated voltage in kV;
+    /// errors if the short-circuit power is zero
+    pub fn impedance(&self, rated_voltage: f64) -> Result<(f64, f64), Error> {
+        if self.sk == 0.0 {
+            return Err(Error::InvalidArgument(
+                "Short-circuit power has to be set".to_string(),
+            ));
+        }
+        let z = rated_voltage * rated_voltage / self.sk;
+        let x = z / (1.0 + self.rx * self.rx).sqrt();
+        Ok((self.rx * x, x))
+    }
+
+    /// Get a parameter by name
+    pub fn get(&self, name: &str) -> Result<f64, Error> {
+        match name {
+            "sk" => Ok(self.sk),
+            "rx" => Ok(self.rx),
+            "v" => Ok(self.v),
+            _ => Err(Error::NotFound {
+                kind: "parameter",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Set a parameter by name; errors if the value is negative or not finite
+    pub fn set(&mut self, name: &str, value: f64) -> Result<(), Error> {
+        let p = match name {
+            "sk" => &mut self.sk,
+            "rx" => &mut self.rx,
+            "v" => &mut self.v,
+            _ => {
+                return Err(Error::NotFound {
+                    kind: "parameter",
+                    name: name.to_string(),
+                })
+            }
+        };
+        if !value.is_finite() || value < 0.0 {
+            return Err(Error::InvalidArgument(format!(
+                "Parameter {} has to be a positive number",
+                name
+            )));
+        }
+        *p = value;
+        Ok(())
+    }
+}
+
+impl Default for ExternalGridParameters {
+    fn default() -> Self {
+        ExternalGridParameters {
+            sk: 0.0,
+            rx: 0.1,
+            v: 1.0,
+        }
+    }
+}
+
+/// Connection of a transformer winding
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum WindingConnection {
+    /// Star
+    Y,
+    /// Star with the neutral brought out
+    YN,
+    /// Delta
+    D,
+    /// Zigzag
+    Z,
+    /// Zigzag with the neutral brought out
+    ZN,
+}
+
+impl fmt::Display for WindingConnection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Vector group of a transformer, e.g. Dyn11 or YNyn0d5
+///
+/// The first winding is the high voltage winding; the phase shift of each other winding is given as a clock number,
+/// in steps of 30 degrees.
+#[derive(Debug, PartialEq, Clone)]
+pub struct VectorGroup {
+    connections: Vec<WindingConnection>,
+    clocks: Vec<u8>,
+}
+
+impl VectorGroup {
+    /// Parse a vector group in IEC 60076-1 notation; errors if it has fewer than two or more than three windings
+    pub fn parse(group: &str) -> Result<VectorGroup, Error> {
+        let invalid = || Error::InvalidArgument(format!("Invalid vector group {}", group));
+        let mut connections = vec![];
+        let mut clocks = vec![];
+        let mut chars = group.chars().peekable();
+        while let Some(c) = chars.next() {
+            let high = connections.is_empty();
+            if c.is_ascii_uppercase() != high {
+                return Err(invalid());
+            }
+            let neutral = chars.next_if(|n| n.eq_ignore_ascii_case(&'n')).is_some();
+            let connection = match (c.to_ascii_uppercase(), neutral) {
+                ('Y', false) => WindingConnection::Y,
+                ('Y', true) => WindingConnection::YN,
+                ('D', false) => WindingConnection::D,
+                ('Z', false) => WindingConnection::Z,
+                ('Z', true) => WindingConnection::ZN,
+                _ => return Err(invalid()),
+            };
+            connections.push(connection);
+            if !high {
+                let mut clock = String::new();
+                while let Some(d) = chars.next_if(|d| d.is_ascii_digit()) {
+                    clock.push(d);
+                }
+                match clock.parse::<u8>() {
+                    Ok(clock) if clock < 12 => clocks.push(clock),
+                    _ => return Err(invalid()),
+                }
+            }
+        }
+        if !(2..=3).contains(&connections.len()) {
+            return Err(invalid());
+        }
+        Ok(VectorGroup {
+            connections,
+            clocks,
+        })
+    }
+
+    /// Return the number of windings
+    pub fn windings(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Return the connection of each winding
+    pub fn connections(&self) -> &Vec<WindingConnection> {
+        &self.connections
+    }
+
+    /// Return the phase shift in degrees of the given winding with respect to the first winding
+    pub fn phase_shift(&self, winding: usize) -> Option<f64> {
+        match winding {
+            0 => Some(0.0),
+            w => self.clocks.get(w - 1).map(|c| *c as f64 * 30.0),
+        }
+    }
+}
+
+impl fmt::Display for VectorGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.connections[0])?;
+        for (connection, clock) in self.connections[1..].iter().zip(&self.clocks) {
+            write!(f, "{}{}", connection.to_string().to_lowercase(), clock)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ratings and short-circuit impedance of a two- or three-winding transformer
+///
+/// The rated power is in MVA and the rated voltage of each winding in kV. The short-circuit voltage of each
+/// pair of windings is in percent, and sets the impedance together with the X/R ratio.
+/// The parameters of the on-load [TapChanger] are included.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TransformerParameters {
+    sn: f64,
+    u: [f64; 3],
+    uk: [f64; 3],
+    xr: f64,
+    vector_group: VectorGroup,
+    tap: TapChanger,
+}
+
+impl TransformerParameters {
+    /// Names of the parameters of a two-winding transformer, as used by [TransformerParameters::get] and [TransformerParameters::set]
+    pub const TWO_WINDING_NAMES: [&'static str; 8] = [
+        "sn", "u1", "u2", "uk", "xr", "tap_min", "tap_max", "tap_step",
+    ];
+    /// Names of the parameters of a three-winding transformer
+    pub const THREE_WINDING_NAMES: [&'static str; 11] = [
+        "sn", "u1", "u2", "u3", "uk12", "uk13", "uk23", "xr", "tap_min", "tap_max", "tap_step",
+    ];
+
+    /// Constructor of the parameters of a two-winding transformer, with vector group YNyn0
+    pub fn two_winding() -> TransformerParameters {
+        TransformerParameters::with_vector_group("YNyn0")
+    }
+
+    /// Constructor of the parameters of a three-winding transformer, with vector group YNyn0d11
+    pub fn three_winding() -> TransformerParameters {
+        TransformerParameters::with_vector_group("YNyn0d11")
+    }
+
+    fn with_vector_group(group: &str) -> TransformerParameters {
+        TransformerParameters {
+            sn: 0.0,
+            u: [0.0; 3],
+            uk: [0.0; 3],
+            xr: 0.0,
+            vector_group: VectorGroup::parse(group).unwrap(),
+            tap: TapChanger::default(),
+        }
+    }
+
+    /// Return the names of the parameters
+    pub fn names(&self) -> &'static [&'static str] {
+        match self.windings() {
+            2 => &TransformerParameters::TWO_WINDING_NAMES,
+            _ => &TransformerParameters::THREE_WINDING_NAMES,
+        }
+    }
+
+    /// Return the number of windings, two or three
+    pub fn windings(&self) -> usize {
+        self.vector_group.windings()
+    }
+
+    /// Return the rated power
+    pub fn sn(&self) -> f64 {
+        self.sn
+    }
+
+    /// Return the X/R ratio
+    pub fn xr(&self) -> f64 {
+        self.xr
+    }
+
+    /// Return the [VectorGroup]
+    pub fn vector_group(&self) -> &VectorGroup {
+        &self.vector_group
+    }
+
+    /// Set the [VectorGroup]; errors if its number of windings differs
+    pub fn set_vector_group(&mut self, group: VectorGroup) -> Result<(), Error> {
+        if group.windings() != self.windings() {
+            return Err(Error::InvalidArgument(format!(
+                "Vector group {} does not have {} windings",
+                group,
+                self.windings()
+            )));
+        }
+        self.vector_group = group;
+        Ok(())
+    }
+
+    /// Return the [TapChanger]
+    pub fn tap_changer(&self) -> &TapChanger {
+        &self.tap
+    }
+
+    /// Return the [TapChanger] to set its position; tap changes go through [Network::raise_tap](super::network::Network::raise_tap)
+    pub(crate) fn tap_changer_mut(&mut self) -> &mut TapChanger {
+        &mut self.tap
+    }
+
+    /// Return the rated voltage of a winding, counting from 0
+    pub fn rated_voltage(&self, winding: usize) -> Result<f64, Error> {
+        self.check_winding(winding)?;
+        Ok(self.u[winding])
+    }
+
+    /// Return the short-circuit voltage between two windings
+    pub fn uk(&self, a: usize, b: usize) -> Result<f64, Error> {
+        self.check_winding(a)?;
+        self.check_winding(b)?;
+        match (a.min(b), a.max(b)) {
+            (0, 1) => Ok(self.uk[0]),
+            (0, 2) => Ok(self.uk[1]),
+            (1, 2) => Ok(self.uk[2]),
+            _ => Err(Error::InvalidArgument(format!(
+                "Winding {} and {} are not a pair",
+                a, b
+            ))),
+        }
+    }
+
+    /// Return the rated voltage ratio between two windings
+    pub fn ratio(&self, a: usize, b: usize) -> Result<f64, Error> {
+        let (ua, ub) = (self.rated_voltage(a)?, self.rated_voltage(b)?);
+        if ub == 0.0 {
+            return Err(Error::InvalidArgument(format!(
+                "Rated voltage of winding {} has to be set",
+                b
+            )));
+        }
+        Ok(ua / ub)
+    }
+
+    /// Return the voltage ratio between two windings at the current tap position
+    pub fn effective_ratio(&self, a: usize, b: usize) -> Result<f64, Error> {
+        let factor = |winding| match winding {
+            0 => self.tap.factor(),
+            _ => 1.0,
+        };
+        Ok(self.ratio(a, b)? * factor(a) / factor(b))
+    }
+
+    /// Return the short-circuit resistance and reactance in ohm between two windings, referred to winding a
+    pub fn impedance(&self, a: usize, b: usize) -> Result<(f64, f64), Error> {
+        let uk = self.uk(a, b)?;
+        if self.sn == 0.0 {
+            return Err(Error::InvalidArgument(
+                "Rated power has to be set".to_string(),
+            ));
+        }
+        let u = self.u[a];
+        let z = uk / 100.0 * u * u / self.sn;
+        let r = z / (1.0 + self.xr * self.xr).sqrt();
+        Ok((r, r * self.xr))
+    }
+
+    /// Get a parameter by name
+    pub fn get(&self, name: &str) -> Result<f64, Error> {
+        if TapChanger::NAMES.contains(&name) {
+            return self.tap.get(name);
+        }
+        let windings = self.windings();
+        match self.field(name) {
+            Some(i) => Ok(match i {
+                0 => self.sn,
+                1..=3 => self.u[i - 1],
+                4..=6 => self.uk[i - 4],
+                _ => self.xr,
+            }),
+            None => Err(Error::NotFound {
+                kind: "parameter",
+                name: format!("{} of a {}-winding transformer", name, windings),
+            }),
+        }
+    }
+
+    /// Set a parameter by name; errors if the value is negative or not finite,
+    /// or invalid for the tap changer, see [TapChanger::set]
+    pub fn set(&mut self, name: &str, value: f64) -> Result<(), Error> {
+        if TapChanger::NAMES.contains(&name) {
+            return self.tap.set(name, value);
+        }
+        self.get(name)?;
+        if !value.is_finite() || value < 0.0 {
+            return Err(Error::InvalidArgument(format!(
+                "Parameter {} has to be a positive number",
+                name
+            )));
+        }
+        let p = match self.field(name) {
+            Some(0) => &mut self.sn,
+            Some(i @ 1..=3) => &mut self.u[i - 1],
+            Some(i @ 4..=6) => &mut self.uk[i - 4],
+            _ => &mut self.xr,
+        };
+        *p = value;
+        Ok(())
+    }
+
+    /// Return the index of the named parameter in sn, u, uk, xr, if the transformer has it
+    fn field(&self, name: &str) -> Option<usize> {
+        if !self.names().contains(&name) {
+            return None;
+        }
+        match name {
+            "sn" => Some(0),
+            "u1" => Some(1),
+            "u2" => Some(2),
+            "u3" => Some(3),
+            "uk" | "uk12" => Some(4),
+            "uk13" => Some(5),
+            "uk23" => Some(6),
+            _ => Some(7),
+        }
+    }
+
+    fn check_winding(&self, winding: usize) -> Result<(), Error> {
+        if winding >= self.windings() {
+            return Err(Error::InvalidArgument(format!(
+                "Transformer has {} windings",
+                self.windings()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::*;
+    use crate::network::Network;
+
+    #[test]
+    fn parameters_line() {
+        let mut p = LineParameters::new(0.05, 0.4, 2.8e-6, 12.5, 800.0).unwrap();
+        assert_eq!(p.impedance(), (0.625, 5.0));
+        assert_eq!(p.susceptance(), 3.5e-5);
+        assert_eq!(p.rating(), 800.0);
+
+        p.set("length", 10.0).unwrap();
+        assert_eq!(p.get("length").unwrap(), 10.0);
+        assert!(p.set("length", -1.0).is_err());
+        assert!(p.set("r", f64::NAN).is_err());
+        assert!(p.set("c", 1.0).is_err());
+        assert!(p.get("c").is_err());
+        assert!(LineParameters::new(0.05, 0.4, 2.8e-6, 12.5, -800.0).is_err());
+    }
+
+    #[test]
+    fn parameters_load() {
+        let mut l = LoadParameters::new(10.0, -2.0, LoadModel::ConstantPower).unwrap();
+        assert_eq!(l.demand(0.9), (10.0, -2.0));
+        l.set_model(LoadModel::ConstantImpedance);
+        assert_eq!(l.demand(0.5), (2.5, -0.5));
+        assert_eq!(l.model().to_string(), "Constant Impedance");
+
+        l.set("p", 20.0).unwrap();
+        assert_eq!(l.get("p").unwrap(), 20.0);
+        assert!(l.set("q", f64::INFINITY).is_err());
+        assert!(l.set("v", 1.0).is_err());
+        assert!(LoadParameters::new(f64::NAN, 0.0, LoadModel::ConstantPower).is_err());
+    }
+
+    #[test]
+    fn parameters_infeed() {
+        let mut g = GeneratorParameters::default();
+        assert_eq!(g.v(), 1.0);
+        assert!(g.set("v", 0.0).is_err());
+        g.set("q_max", 30.0).unwrap();
+        g.set("q_min", -20.0).unwrap();
+        assert_eq!(g.limit_q(50.0), 30.0);
+        assert_eq!(g.limit_q(-50.0), -20.0);
+        assert_eq!(g.limit_q(5.0), 5.0);
+        assert!(GeneratorParameters::new(100.0, 1.02, -20.0, 30.0).is_ok());
+
+        let mut e = ExternalGridParameters::new(4000.0, 0.0, 1.0).unwrap();
+        assert_eq!(e.impedance(400.0).unwrap(), (0.0, 40.0));
+        e.set("rx", 0.75).unwrap();
+        let (r, x) = e.impedance(400.0).unwrap();
+        assert!((r - 24.0).abs() < 1e-9 && (x - 32.0).abs() < 1e-9);
+        assert!(e.set("sk", -1.0).is_err());
+        assert!(ExternalGridParameters::default().impedance(400.0).is_err());
+    }
+
+    #[test]
+    fn parameters_transformer() {
+        let group = VectorGroup::parse("YNyn0d5").unwrap();
+        assert_eq!(group.windings(), 3);
+        assert_eq!(group.connections()[1], WindingConnection::YN);
+        assert_eq!(group.phase_shift(2), Some(150.0));
+        assert_eq!(group.to_string(), "YNyn0d5");
+        assert_eq!(VectorGroup::parse("Dzn11").unwrap().to_string(), "Dzn11");
+        for invalid in ["", "YN", "yd11", "YD11", "Yd", "Yd12", "Xd1", "Yy0d1d1"] {
+            assert!(VectorGroup::parse(invalid).is_err(), "{}", invalid);
+        }
+
+        let mut t = TransformerParameters::two_winding();
+        assert_eq!(t.windings(), 2);
+        assert_eq!(t.names().len(), 8);
+        t.set("sn", 100.0).unwrap();
+        t.set("u1", 150.0).unwrap();
+        t.set("u2", 15.0).unwrap();
+        t.set("uk", 10.0).unwrap();
+        assert!(t.set("u3", 10.0).is_err());
+        assert!(t.set("xr", -1.0).is_err());
+        assert_eq!(t.ratio(0, 1).unwrap(), 10.0);
+        assert_eq!(t.impedance(0, 1).unwrap(), (22.5, 0.0));
+        t.set("xr", 1.0).unwrap();
+        let (r, x) = t.impedance(1, 0).unwrap();
+        assert!((r - 0.225 / 2f64.sqrt()).abs() < 1e-12 && (r - x).abs() < 1e-12);
+        assert!(t.uk(0, 2).is_err());
+        assert!(t.set_vector_group(group.clone()).is_err());
+        t.set_vector_group(VectorGroup::parse("Dyn11").unwrap())
+            .unwrap();
+
+        t.set("tap_max", 8.0).unwrap();
+        t.set("tap_step", 2.5).unwrap();
+        assert_eq!(t.get("tap_max").unwrap(), 8.0);
+        assert_eq!(t.ratio(0, 1).unwrap(), 10.0);
+        assert_eq!(t.effective_ratio(0, 1).unwrap(), 10.0);
+
+        let net = Network::new("net");
+        net.create_component::<Transformer>("tf").unwrap();
+        for (name, value) in [
+            ("u1", 150.0),
+            ("u2", 15.0),
+            ("tap_max", 8.0),
+            ("tap_step", 2.5),
+        ] {
+            net.set_parameter("tf", name, value).unwrap();
+        }
+        net.raise_tap("tf").unwrap();
+        net.raise_tap("tf").unwrap();
+        net.raise_tap("tf").unwrap();
+        net.lower_tap("tf").unwrap();
+        let tf = net.get_component("tf").unwrap();
+        let t = tf.transformer_parameters().unwrap().read().unwrap();
+        assert!((t.effective_ratio(0, 1).unwrap() - 10.5).abs() < 1e-12);
+        assert!((t.effective_ratio(1, 0).unwrap() - 1.0 / 10.5).abs() < 1e-12);
+        assert_eq!(t.ratio(0, 1).unwrap(), 10.0);
+
+        let mut t = TransformerParameters::three_winding();
+        assert_eq!(t.vector_group().to_string(), "YNyn0d11");
+        t.set("uk23", 6.0).unwrap();
+        assert_eq!(t.uk(2, 1).unwrap(), 6.0);
+        assert!(t.get("uk").is_err());
+        assert!(t.ratio(0, 2).is_err());
+        assert!(t.impedance(0, 1).is_err());
+        t.set_vector_group(group).unwrap();
+    }
+}
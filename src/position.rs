@@ -1,12 +1,42 @@
-#[derive(PartialEq)]
-enum SwitchPosition {
+use std::fmt;
+use std::time::Duration;
+
+use super::error::Error;
+
+/// Double-point position of a switch
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SwitchPosition {
+    /// Open; does not conduct
     Open,
-    Close,
+    /// Closed; conducts
+    Closed,
+    /// Travelling between open and closed
+    Intermediate,
+    /// Faulty or invalid status reported by the field equipment
+    Bad,
+}
+
+impl fmt::Display for SwitchPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let posstr = match self {
+            SwitchPosition::Open => "Open",
+            SwitchPosition::Closed => "Closed",
+            SwitchPosition::Intermediate => "Intermediate",
+            SwitchPosition::Bad => "Bad",
+        };
+        write!(f, "{}", posstr)
+    }
 }
 
 /// Switchgear Position
+///
+/// Switchgear can only be operated from a definite position; opened when closed and closed when open.
+/// Telemetry can report any [SwitchPosition], see [SwitchgearPosition::report].
+/// Switchgear with an operating time travels through [SwitchPosition::Intermediate] when operated through a
+/// [Network](super::network::Network), see [Network::advance](super::network::Network::advance).
 pub struct SwitchgearPosition {
     position: SwitchPosition,
+    operating_time: Duration,
 }
 
 impl SwitchgearPosition {
@@ -14,12 +44,28 @@ impl SwitchgearPosition {
     pub fn new() -> SwitchgearPosition {
         SwitchgearPosition {
             position: SwitchPosition::Open,
+            operating_time: Duration::ZERO,
         }
     }
 
+    /// Return the time an operation takes to complete; zero if it completes immediately
+    pub fn operating_time(&self) -> Duration {
+        self.operating_time
+    }
+
+    /// Set the time an operation takes to complete
+    pub fn set_operating_time(&mut self, time: Duration) {
+        self.operating_time = time;
+    }
+
+    /// Return the current position
+    pub fn position(&self) -> SwitchPosition {
+        self.position
+    }
+
     /// True if closed
     pub fn is_closed(&self) -> bool {
-        self.position == SwitchPosition::Close
+        self.position == SwitchPosition::Closed
     }
 
     /// True if open
@@ -27,25 +73,79 @@ impl SwitchgearPosition {
         self.position == SwitchPosition::Open
     }
 
-    /// Close and error if already closed
-    pub fn close(&mut self) -> Result<(), String> {
-        if self.is_closed() {
-            Err("Switchgear already closed".to_string())
-        } else {
-            self.position = SwitchPosition::Close;
-            Ok(())
+    /// True if the switch may conduct; closed, travelling or faulty
+    pub fn may_conduct(&self) -> bool {
+        self.position != SwitchPosition::Open
+    }
+
+    /// Close; errors if already closed or not in a definite position
+    pub fn close(&mut self) -> Result<(), Error> {
+        self.check_close()?;
+        self.position = SwitchPosition::Closed;
+        Ok(())
+    }
+
+    /// Check that the switchgear can be closed, see [SwitchgearPosition::close]
+    pub(crate) fn check_close(&self) -> Result<(), Error> {
+        match self.position {
+            SwitchPosition::Open => Ok(()),
+            SwitchPosition::Closed => Err(Error::AlreadyClosed),
+            from => Err(Error::InvalidTransition {
+                from,
+                to: SwitchPosition::Closed,
+            }),
+        }
+    }
+
+    /// Open; errors if already open or not in a definite position
+    pub fn open(&mut self) -> Result<(), Error> {
+        self.check_open()?;
+        self.position = SwitchPosition::Open;
+        Ok(())
+    }
+
+    /// Check that the switchgear can be opened, see [SwitchgearPosition::open]
+    pub(crate) fn check_open(&self) -> Result<(), Error> {
+        match self.position {
+            SwitchPosition::Closed => Ok(()),
+            SwitchPosition::Open => Err(Error::AlreadyOpen),
+            from => Err(Error::InvalidTransition {
+                from,
+                to: SwitchPosition::Open,
+            }),
         }
     }
 
-    /// Open and error if already open
-    pub fn open(&mut self) -> Result<(), String> {
-        if self.is_open() {
-            Err("Switchgear already open".to_string())
-        } else {
-            self.position = SwitchPosition::Open;
-            Ok(())
+    /// Apply a position reported by telemetry. The field equipment is the authority on its own position,
+    /// so any change is accepted except a bad status turning into travelling; a switch has to report a
+    /// definite position before it can travel again.
+    pub fn report(&mut self, position: SwitchPosition) -> Result<(), Error> {
+        self.check_report(position)?;
+        self.position = position;
+        Ok(())
+    }
+
+    /// Check that a position reported by telemetry can be applied, see [SwitchgearPosition::report]
+    pub(crate) fn check_report(&self, position: SwitchPosition) -> Result<(), Error> {
+        match (self.position, position) {
+            (SwitchPosition::Bad, SwitchPosition::Intermediate) => Err(Error::InvalidTransition {
+                from: self.position,
+                to: position,
+            }),
+            _ => Ok(()),
         }
     }
+
+    /// Set the position without checking the transition; used to revert changes
+    pub(crate) fn set(&mut self, position: SwitchPosition) {
+        self.position = position;
+    }
+}
+
+impl Default for SwitchgearPosition {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -58,13 +158,40 @@ mod tests {
 
         assert!(pos.is_open());
         assert!(!pos.is_closed());
+        assert_eq!(pos.position(), SwitchPosition::Open);
         pos.close().unwrap();
-        assert!(pos.close().is_err());
+        assert_eq!(pos.position(), SwitchPosition::Closed);
+        assert!(matches!(pos.close(), Err(Error::AlreadyClosed)));
         assert!(!pos.is_open());
         assert!(pos.is_closed());
         pos.open().unwrap();
-        assert!(pos.open().is_err());
+        assert!(matches!(pos.open(), Err(Error::AlreadyOpen)));
         assert!(pos.is_open());
         assert!(!pos.is_closed());
     }
+
+    #[test]
+    fn switchgear_position_telemetry() {
+        let mut pos = SwitchgearPosition::new();
+        assert!(!pos.may_conduct());
+
+        pos.report(SwitchPosition::Intermediate).unwrap();
+        assert!(pos.may_conduct());
+        assert!(!pos.is_open() && !pos.is_closed());
+        assert!(matches!(
+            pos.close(),
+            Err(Error::InvalidTransition {
+                from: SwitchPosition::Intermediate,
+                to: SwitchPosition::Closed
+            })
+        ));
+        assert!(pos.open().is_err());
+
+        pos.report(SwitchPosition::Bad).unwrap();
+        assert!(pos.report(SwitchPosition::Intermediate).is_err());
+        assert!(pos.close().is_err());
+        pos.report(SwitchPosition::Closed).unwrap();
+        pos.open().unwrap();
+        assert_eq!(pos.position(), SwitchPosition::Open);
+    }
 }
@@ -0,0 +1,376 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use super::component::*;
+use super::error::Error;
+use super::network::{ComponentId, Network, NodeId};
+
+/// A single step of a switching program
+#[derive(Debug, PartialEq, Clone)]
+pub enum Step {
+    /// Open the switchgear with the given name
+    Open(String),
+    /// Close the switchgear with the given name
+    Close(String),
+    /// Check that the voltage transformer with the given name measures no voltage
+    VerifyNoVoltage(String),
+}
+
+impl Step {
+    /// Return the step that undoes this one; verification steps have no inverse
+    pub fn reverse(&self) -> Option<Step> {
+        match self {
+            Step::Open(name) => Some(Step::Close(name.clone())),
+            Step::Close(name) => Some(Step::Open(name.clone())),
+            Step::VerifyNoVoltage(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Step::Open(name) => write!(f, "Open {}", name),
+            Step::Close(name) => write!(f, "Close {}", name),
+            Step::VerifyNoVoltage(name) => write!(f, "Verify no voltage on {}", name),
+        }
+    }
+}
+
+/// Switching programs to isolate and earth a component for work, and to restore it afterwards
+pub struct Procedure {
+    target: String,
+    isolation: Vec<Step>,
+    restoration: Vec<Step>,
+}
+
+impl Procedure {
+    /// Generate the isolation and restoration programs for the component with the given name.
+    ///
+    /// The isolated section is the target plus everything reachable from it without passing switchgear.
+    /// The isolation program
+    /// 1. Opens the closed circuit breakers on the section boundary, and those in the same bay as the boundary disconnectors
+    /// 2. Opens the closed disconnectors on the section boundary
+    /// 3. Verifies no voltage on each voltage transformer in the section
+    /// 4. Closes the open earthing switches in the section
+    ///
+    /// The restoration program undoes the isolation program in reverse order.
+    /// Returns an error if a source node, generator or external grid lies within the section, as it can not be isolated.
+    pub fn isolation(net: &Network, target: &str) -> Result<Procedure, Error> {
+        let id = net.component_id(target)?;
+        let t = net.component(id)?;
+        let sources = net.source_ids();
+
+        let mut breakers: Vec<ComponentId> = vec![];
+        let mut disconnectors: Vec<ComponentId> = vec![];
+        let mut vts: Vec<ComponentId> = vec![];
+        let mut earths: Vec<ComponentId> = vec![];
+        match t.r#type() {
+            ComponentType::CircuitBreaker => breakers.push(id),
+            ComponentType::Disconnector => disconnectors.push(id),
+            _ => {}
+        }
+
+        let mut visited: HashSet<ComponentId> = HashSet::new();
+        visited.insert(id);
+        let mut visited_nodes: HashSet<NodeId> = HashSet::new();
+        let mut queue: VecDeque<NodeId> = t.nodes().into_iter().collect();
+        while let Some(node) = queue.pop_front() {
+            if !visited_nodes.insert(node) {
+                continue;
+            }
+            if sources.contains(&node) {
+                return Err(Error::InvalidArgument(format!(
+                    "{} can not be isolated from source node {}",
+                    target,
+                    net.node(node)?.name()
+                )));
+            }
+            for cid in net.node_components(node)? {
+                if !visited.insert(cid) {
+                    continue;
+                }
+                let c = net.component(cid)?;
+                match c.r#type() {
+                    ComponentType::CircuitBreaker => breakers.push(cid),
+                    ComponentType::Disconnector => {
+                        disconnectors.push(cid);
+                        let far = c.nodes().into_iter().filter(|n| *n != node).collect();
+                        for b in bay_breakers(net, far, &visited_nodes)? {
+                            if visited.insert(b) {
+                                breakers.push(b);
+                            }
+                        }
+                    }
+                    ComponentType::EarthingSwitch => earths.push(cid),
+                    ComponentType::VoltageTransformer => vts.push(cid),
+                    ComponentType::Transformer
+                    | ComponentType::ThreeWindingTransformer
+                    | ComponentType::Line
+                    | ComponentType::Cable => queue.extend(c.nodes()),
+                    ComponentType::Generator | ComponentType::ExternalGrid => {
+                        return Err(Error::InvalidArgument(format!(
+                            "{} can not be isolated from infeed {}",
+                            target,
+                            c.name()
+                        )))
+                    }
+                    ComponentType::Load => {}
+                }
+            }
+        }
+
+        let mut isolation = vec![];
+        for cid in breakers.iter().chain(disconnectors.iter()) {
+            let c = net.component(*cid)?;
+            if c.position()?.read().unwrap().is_closed() {
+                isolation.push(Step::Open(c.name().clone()));
+            }
+        }
+        for cid in vts {
+            isolation.push(Step::VerifyNoVoltage(net.component(cid)?.name().clone()));
+        }
+        for cid in earths {
+            let c = net.component(cid)?;
+            if c.position()?.read().unwrap().is_open() {
+                isolation.push(Step::Close(c.name().clone()));
+            }
+        }
+        let restoration = isolation.iter().rev().filter_map(Step::reverse).collect();
+
+        Ok(Procedure {
+            target: target.to_string(),
+            isolation,
+            restoration,
+        })
+    }
+
+    /// Return the name of the component the procedure isolates
+    pub fn target(&self) -> &String {
+        &self.target
+    }
+
+    /// Return the ordered steps that isolate and earth the target
+    pub fn isolation_steps(&self) -> &Vec<Step> {
+        &self.isolation
+    }
+
+    /// Return the ordered steps that restore the network to its state before isolation
+    pub fn restoration_steps(&self) -> &Vec<Step> {
+        &self.restoration
+    }
+}
+
+/// Get the circuit breakers in the same bay as a boundary disconnector, walking from its far nodes through
+/// disconnectors and plain nodes. The walk stops at busbar sections and at the section being isolated,
+/// so the breakers of other bays on a shared busbar, and bus couplers, are left alone.
+fn bay_breakers(
+    net: &Network,
+    start: Vec<NodeId>,
+    section: &HashSet<NodeId>,
+) -> Result<Vec<ComponentId>, Error> {
+    let mut breakers = vec![];
+    let mut visited: HashSet<NodeId> = HashSet::new();
+    let mut queue: VecDeque<NodeId> = start.into();
+    while let Some(node) = queue.pop_front() {
+        if section.contains(&node) || net.busbar_of(node).is_some() || !visited.insert(node) {
+            continue;
+        }
+        for cid in net.node_components(node)? {
+            let c = net.component(cid)?;
+            match c.r#type() {
+                ComponentType::CircuitBreaker => breakers.push(cid),
+                ComponentType::Disconnector => queue.extend(c.nodes()),
+                _ => {}
+            }
+        }
+    }
+    Ok(breakers)
+}
+
+impl Network {
+    /// Generate the switching programs to isolate the component with the given name, see [Procedure::isolation]
+    pub fn isolation_procedure(&self, name: &str) -> Result<Procedure, Error> {
+        Procedure::isolation(self, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// source - cb - node1 - ds1 - node2 - tf - node3 - ds2 - node4,
+    /// with earthing switches on node2 and node3 and a voltage transformer on node3
+    fn create_test_network() -> Network {
+        let net = Network::new("net");
+        for n in ["source", "node1", "node2", "node3", "node4"] {
+            net.create_node(n).unwrap();
+        }
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds1").unwrap();
+        net.create_component::<Disconnector>("ds2").unwrap();
+        net.create_component::<Transformer>("tf").unwrap();
+        net.create_component::<EarthingSwitch>("es1").unwrap();
+        net.create_component::<EarthingSwitch>("es2").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.connect("source", "cb", 0).unwrap();
+        net.connect("node1", "cb", 1).unwrap();
+        net.connect("node1", "ds1", 0).unwrap();
+        net.connect("node2", "ds1", 1).unwrap();
+        net.connect("node2", "tf", 0).unwrap();
+        net.connect("node3", "tf", 1).unwrap();
+        net.connect("node3", "ds2", 0).unwrap();
+        net.connect("node4", "ds2", 1).unwrap();
+        net.connect("node2", "es1", 0).unwrap();
+        net.connect("node3", "es2", 0).unwrap();
+        net.connect("node3", "vt", 0).unwrap();
+        net.add_source("source").unwrap();
+        for s in ["cb", "ds1", "ds2"] {
+            net.close(s).unwrap();
+        }
+        net
+    }
+
+    #[test]
+    fn procedure_isolation() {
+        let net = create_test_network();
+        let p = net.isolation_procedure("tf").unwrap();
+        assert_eq!(p.target(), "tf");
+        assert_eq!(
+            p.isolation_steps(),
+            &vec![
+                Step::Open("cb".to_string()),
+                Step::Open("ds1".to_string()),
+                Step::Open("ds2".to_string()),
+                Step::VerifyNoVoltage("vt".to_string()),
+                Step::Close("es1".to_string()),
+                Step::Close("es2".to_string()),
+            ]
+        );
+        assert_eq!(
+            p.restoration_steps(),
+            &vec![
+                Step::Open("es2".to_string()),
+                Step::Open("es1".to_string()),
+                Step::Close("ds2".to_string()),
+                Step::Close("ds1".to_string()),
+                Step::Close("cb".to_string()),
+            ]
+        );
+        assert_eq!(
+            p.isolation_steps()[3].to_string(),
+            "Verify no voltage on vt"
+        );
+
+        for step in p.isolation_steps() {
+            match step {
+                Step::Open(name) => net.open(name).unwrap(),
+                Step::Close(name) => net.close(name).unwrap(),
+                Step::VerifyNoVoltage(_) => {}
+            }
+        }
+        let e = net.energization();
+        assert!(e.earth_faults().is_empty());
+        assert_eq!(
+            e.component_state("tf").unwrap(),
+            crate::analysis::EnergizationState::Earthed
+        );
+    }
+
+    #[test]
+    fn procedure_isolation_switchgear() {
+        let net = create_test_network();
+        net.open("ds2").unwrap();
+        let p = net.isolation_procedure("ds1").unwrap();
+        assert_eq!(
+            p.isolation_steps(),
+            &vec![
+                Step::Open("cb".to_string()),
+                Step::Open("ds1".to_string()),
+                Step::VerifyNoVoltage("vt".to_string()),
+                Step::Close("es1".to_string()),
+                Step::Close("es2".to_string()),
+            ]
+        );
+
+        assert!(net.isolation_procedure("cb").is_err());
+        assert!(net.isolation_procedure("does not exist").is_err());
+
+        net.create_component::<Load>("load").unwrap();
+        net.connect("node3", "load", 0).unwrap();
+        assert!(net.isolation_procedure("tf").is_ok());
+        net.create_component::<Generator>("gen").unwrap();
+        net.connect("node3", "gen", 0).unwrap();
+        match net.isolation_procedure("tf") {
+            Err(e) => assert_eq!(e.to_string(), "tf can not be isolated from infeed gen"),
+            Ok(_) => panic!("isolated a section with an infeed"),
+        }
+    }
+
+    #[test]
+    fn procedure_isolation_busbar() {
+        // Three feeder bays on a two-section busbar: bb - dsb - bay - cb - feeder - dsl - far
+        let net = Network::new("net");
+        net.create_busbar("bb", 110.0, 2).unwrap();
+        net.add_source("bb.1").unwrap();
+        net.add_coupler("coupler", "bb.1", "bb.2").unwrap();
+        net.close("coupler").unwrap();
+        let switch = |kind: ComponentType, name: &str, a: &str, b: &str| {
+            match kind {
+                ComponentType::CircuitBreaker => net.create_component::<CircuitBreaker>(name),
+                _ => net.create_component::<Disconnector>(name),
+            }
+            .unwrap();
+            net.connect(a, name, 0).unwrap();
+            net.connect(b, name, 1).unwrap();
+            net.close(name).unwrap();
+        };
+        for (bay, section) in [("1", "bb.1"), ("2", "bb.1"), ("3", "bb.2")] {
+            let node = |n: &str| format!("{}{}", n, bay);
+            for n in ["bay", "feeder", "far"] {
+                net.create_node(&node(n)).unwrap();
+            }
+            switch(
+                ComponentType::Disconnector,
+                &node("dsb"),
+                section,
+                &node("bay"),
+            );
+            switch(
+                ComponentType::CircuitBreaker,
+                &node("cb"),
+                &node("bay"),
+                &node("feeder"),
+            );
+            switch(
+                ComponentType::Disconnector,
+                &node("dsl"),
+                &node("feeder"),
+                &node("far"),
+            );
+        }
+
+        // Isolating a breaker leaves the other bays and the coupler on the shared busbar closed
+        let p = net.isolation_procedure("cb1").unwrap();
+        assert_eq!(
+            p.isolation_steps(),
+            &vec![
+                Step::Open("cb1".to_string()),
+                Step::Open("dsb1".to_string()),
+                Step::Open("dsl1".to_string()),
+            ]
+        );
+
+        // Behind a line disconnector, the breaker of its own bay opens first
+        net.create_component::<Line>("line").unwrap();
+        net.connect("far2", "line", 0).unwrap();
+        let p = net.isolation_procedure("line").unwrap();
+        assert_eq!(
+            p.isolation_steps(),
+            &vec![
+                Step::Open("cb2".to_string()),
+                Step::Open("dsl2".to_string())
+            ]
+        );
+    }
+}
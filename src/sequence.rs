@@ -0,0 +1,332 @@
+use std::fmt;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+use super::analysis::{Energization, EnergizationState};
+use super::error::Error;
+use super::json::{JsonReadable, JsonWritable};
+use super::network::Network;
+use super::procedure::Step;
+
+/// Measured voltage in kV above which a voltage transformer reports voltage present
+const VOLTAGE_THRESHOLD: f64 = 0.1;
+
+/// An ordered list of switching steps that can be validated with a dry run and then applied to a [Network]
+///
+/// The text format has one step per line; `open <switchgear>`, `close <switchgear>` or `verify <voltage transformer>`.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sequence {
+    steps: Vec<Step>,
+}
+
+/// Result of a dry run; the energization state after each step up to the first violation
+pub struct DryRun {
+    steps: Vec<(Step, Energization)>,
+    violation: Option<(Step, Error)>,
+}
+
+impl DryRun {
+    /// Return each step that succeeded with the energization state of the network after it
+    pub fn steps(&self) -> &Vec<(Step, Energization)> {
+        &self.steps
+    }
+
+    /// Return the step that failed and why, if any
+    pub fn violation(&self) -> Option<&(Step, Error)> {
+        self.violation.as_ref()
+    }
+
+    /// True if every step succeeded
+    pub fn is_ok(&self) -> bool {
+        self.violation.is_none()
+    }
+}
+
+impl Sequence {
+    /// Constructor; sets the steps
+    pub fn new(steps: Vec<Step>) -> Sequence {
+        Sequence { steps }
+    }
+
+    /// Parse a sequence from text
+    pub fn parse(text: &str) -> Result<Sequence, Error> {
+        let mut steps = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let split: Vec<&str> = line.split_whitespace().collect();
+            let step = match (split[0].to_lowercase().as_str(), split.get(1)) {
+                ("open", Some(name)) => Step::Open(name.to_string()),
+                ("close", Some(name)) => Step::Close(name.to_string()),
+                ("verify", Some(name)) => Step::VerifyNoVoltage(name.to_string()),
+                _ => {
+                    return Err(Error::InvalidArgument(format!(
+                        "Invalid step on line {} of sequence: {}",
+                        i + 1,
+                        line
+                    )))
+                }
+            };
+            steps.push(step);
+        }
+        Ok(Sequence { steps })
+    }
+
+    /// Read a sequence from a text stream
+    pub fn read<R: Read>(mut r: R) -> Result<Sequence, Error> {
+        let mut text = String::new();
+        match r.read_to_string(&mut text) {
+            Ok(_) => Sequence::parse(&text),
+            Err(e) => Err(Error::Io {
+                context: "Failed to read sequence".to_string(),
+                source: e,
+            }),
+        }
+    }
+
+    /// Read a sequence from the file at the given path
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Sequence, Error> {
+        match File::open(&path) {
+            Ok(f) => Sequence::read(f),
+            Err(e) => Err(Error::Io {
+                context: format!("Failed to open sequence file {}", path.as_ref().display()),
+                source: e,
+            }),
+        }
+    }
+
+    /// Return the steps in order
+    pub fn steps(&self) -> &Vec<Step> {
+        &self.steps
+    }
+
+    /// Apply the steps to a copy of the network, stopping at the first interlock or safety violation.
+    /// The given network is left untouched.
+    ///
+    /// The copy starts without history and with the simulation clock at zero, so a dry run is refused
+    /// while operations are in flight; their completion could not be simulated, see [Network::advance].
+    pub fn dry_run(&self, net: &Network) -> Result<DryRun, Error> {
+        let in_flight = net.operations().len();
+        if in_flight > 0 {
+            return Err(Error::InvalidArgument(format!(
+                "Can not dry run while {} operation(s) are in flight",
+                in_flight
+            )));
+        }
+        let mut buf = vec![];
+        net.write_json(&mut buf)?;
+        let copy = Network::read_json(buf.as_slice())?;
+
+        let mut steps = vec![];
+        for step in &self.steps {
+            if let Err(e) = apply(&copy, step) {
+                return Ok(DryRun {
+                    steps,
+                    violation: Some((step.clone(), e)),
+                });
+            }
+            steps.push((step.clone(), copy.energization()));
+        }
+        Ok(DryRun {
+            steps,
+            violation: None,
+        })
+    }
+
+    /// Validate the sequence with a dry run, then apply the steps to the network.
+    /// Returns the violation without changing the network if the dry run fails.
+    ///
+    /// The steps are applied one by one, so if a step still fails, because the network was changed by another thread
+    /// after the dry run, the earlier steps stay applied. The error is returned and they can be reverted with [Network::undo].
+    pub fn run(&self, net: &Network) -> Result<(), Error> {
+        if let Some((_, e)) = self.dry_run(net)?.violation {
+            return Err(e);
+        }
+        for step in &self.steps {
+            apply(net, step)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for step in &self.steps {
+            match step {
+                Step::Open(name) => writeln!(f, "open {}", name)?,
+                Step::Close(name) => writeln!(f, "close {}", name)?,
+                Step::VerifyNoVoltage(name) => writeln!(f, "verify {}", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Apply a single step to the network; switching steps wait for the operation to complete, see [Network::advance].
+/// Verifying fails if the voltage transformer measures voltage above [VOLTAGE_THRESHOLD], or is energized in the model.
+fn apply(net: &Network, step: &Step) -> Result<(), Error> {
+    match step {
+        Step::Open(name) => {
+            net.open(name)?;
+            net.advance(net.operating_time(name)?)
+        }
+        Step::Close(name) => {
+            net.close(name)?;
+            net.advance(net.operating_time(name)?)
+        }
+        Step::VerifyNoVoltage(name) => {
+            let measured = net.get_component(name)?.value()?;
+            let state = net.energization().component_state(name)?;
+            if measured.abs() > VOLTAGE_THRESHOLD || state == EnergizationState::Energized {
+                Err(Error::VoltagePresent(name.to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::*;
+    use std::time::Duration;
+
+    /// source - cb - node1 - ds - node2, with a voltage transformer and an earthing switch on node2
+    fn create_test_network() -> Network {
+        let net = Network::new("net");
+        for n in ["source", "node1", "node2"] {
+            net.create_node(n).unwrap();
+        }
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.create_component::<EarthingSwitch>("es").unwrap();
+        net.connect("source", "cb", 0).unwrap();
+        net.connect("node1", "cb", 1).unwrap();
+        net.connect("node1", "ds", 0).unwrap();
+        net.connect("node2", "ds", 1).unwrap();
+        net.connect("node2", "vt", 0).unwrap();
+        net.connect("node2", "es", 0).unwrap();
+        net.add_source("source").unwrap();
+        net
+    }
+
+    #[test]
+    fn sequence_parse() {
+        let s = Sequence::parse("# energize\nOpen cb\n\n  close ds  \nverify vt\n").unwrap();
+        assert_eq!(
+            s.steps(),
+            &vec![
+                Step::Open("cb".to_string()),
+                Step::Close("ds".to_string()),
+                Step::VerifyNoVoltage("vt".to_string()),
+            ]
+        );
+        assert_eq!(s.to_string(), "open cb\nclose ds\nverify vt\n");
+        assert_eq!(Sequence::parse(&s.to_string()).unwrap(), s);
+
+        let e = Sequence::parse("open cb\nclose\n").unwrap_err();
+        assert_eq!(e.to_string(), "Invalid step on line 2 of sequence: close");
+        assert!(Sequence::parse("toggle cb").is_err());
+        assert!(Sequence::from_file("does not exist.txt").is_err());
+    }
+
+    #[test]
+    fn sequence_dry_run() {
+        let net = create_test_network();
+        let s = Sequence::parse("close ds\nclose cb\nverify vt\nclose es").unwrap();
+
+        let d = s.dry_run(&net).unwrap();
+        assert!(!d.is_ok());
+        assert_eq!(d.steps().len(), 2);
+        assert_eq!(
+            d.steps()[0].1.node_state("node2").unwrap(),
+            EnergizationState::DeEnergized
+        );
+        assert_eq!(
+            d.steps()[1].1.node_state("node2").unwrap(),
+            EnergizationState::Energized
+        );
+        let (step, e) = d.violation().unwrap();
+        assert_eq!(step, &Step::VerifyNoVoltage("vt".to_string()));
+        assert!(matches!(e, Error::VoltagePresent(_)));
+        assert!(net
+            .get_component("ds")
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_open());
+
+        assert!(matches!(s.run(&net), Err(Error::VoltagePresent(_))));
+        assert!(net
+            .get_component("ds")
+            .unwrap()
+            .position()
+            .unwrap()
+            .read()
+            .unwrap()
+            .is_open());
+    }
+
+    #[test]
+    fn sequence_verify_measured() {
+        let net = create_test_network();
+        let s = Sequence::parse("verify vt\nclose es").unwrap();
+        assert!(s.dry_run(&net).unwrap().is_ok());
+
+        net.update("vt", 110.0).unwrap();
+        let d = s.dry_run(&net).unwrap();
+        assert!(d.steps().is_empty());
+        assert!(matches!(d.violation(), Some((_, Error::VoltagePresent(_)))));
+        assert!(matches!(s.run(&net), Err(Error::VoltagePresent(_))));
+
+        net.update("vt", VOLTAGE_THRESHOLD / 2.0).unwrap();
+        s.run(&net).unwrap();
+        assert!(matches!(
+            Sequence::parse("verify cb").unwrap().run(&net),
+            Err(Error::NoMeasurement(_))
+        ));
+    }
+
+    #[test]
+    fn sequence_run() {
+        let net = create_test_network();
+        net.set_operating_time("ds", Duration::from_secs(3))
+            .unwrap();
+        net.close("ds").unwrap();
+        net.advance(Duration::from_secs(3)).unwrap();
+        net.close("cb").unwrap();
+        let p = net.isolation_procedure("vt").unwrap();
+        let s = Sequence::new(p.isolation_steps().clone());
+        assert!(s.dry_run(&net).unwrap().is_ok());
+
+        s.run(&net).unwrap();
+        assert_eq!(net.time(), Duration::from_secs(6));
+
+        net.close("ds").unwrap();
+        assert_eq!(net.operations().len(), 1);
+        assert!(s.dry_run(&net).is_err());
+        net.undo().unwrap();
+        let e = net.energization();
+        assert_eq!(e.node_state("node2").unwrap(), EnergizationState::Earthed);
+        assert_eq!(
+            e.node_state("node1").unwrap(),
+            EnergizationState::DeEnergized
+        );
+
+        Sequence::new(p.restoration_steps().clone())
+            .run(&net)
+            .unwrap();
+        assert_eq!(
+            net.energization().node_state("node2").unwrap(),
+            EnergizationState::Energized
+        );
+    }
+}
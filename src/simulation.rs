@@ -0,0 +1,166 @@
+use std::fmt;
+use std::time::Duration;
+
+use super::interlock::Operation;
+use super::network::ComponentId;
+use super::position::SwitchPosition;
+
+/// A switchgear operation that was started but has not completed yet, see [Network::advance](super::network::Network::advance)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InFlight {
+    component: ComponentId,
+    operation: Operation,
+    completes: Duration,
+}
+
+impl InFlight {
+    /// Constructor
+    pub fn new(component: ComponentId, operation: Operation, completes: Duration) -> InFlight {
+        InFlight {
+            component,
+            operation,
+            completes,
+        }
+    }
+
+    /// Return the id of the operated switchgear
+    pub fn component(&self) -> ComponentId {
+        self.component
+    }
+
+    /// Return the operation
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// Return the simulation time at which the operation completes
+    pub fn completes(&self) -> Duration {
+        self.completes
+    }
+
+    /// Return the position the switchgear starts from
+    pub(crate) fn start(&self) -> SwitchPosition {
+        match self.operation {
+            Operation::Open => SwitchPosition::Closed,
+            Operation::Close => SwitchPosition::Open,
+        }
+    }
+
+    /// Return the position the switchgear ends in; the start position if the operation was cancelled
+    pub(crate) fn end(&self, complete: bool) -> SwitchPosition {
+        match (self.operation, complete) {
+            (Operation::Open, true) | (Operation::Close, false) => SwitchPosition::Open,
+            (Operation::Close, true) | (Operation::Open, false) => SwitchPosition::Closed,
+        }
+    }
+}
+
+impl fmt::Display for InFlight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}, completes at {:.3} s",
+            self.operation,
+            self.component,
+            self.completes.as_secs_f64()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::*;
+    use crate::error::Error;
+    use crate::network::Network;
+
+    #[test]
+    fn simulation_operating_time() {
+        let net = Network::new("net");
+        net.create_node("node").unwrap();
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.create_component::<EarthingSwitch>("es").unwrap();
+        net.connect("node", "cb", 0).unwrap();
+        net.add_source("node").unwrap();
+        let position = |name: &str| {
+            net.get_component(name)
+                .unwrap()
+                .position()
+                .unwrap()
+                .read()
+                .unwrap()
+                .position()
+        };
+
+        assert!(net
+            .set_operating_time("es", Duration::from_secs(1))
+            .is_err());
+        net.set_operating_time("cb", Duration::from_millis(60))
+            .unwrap();
+        net.set_operating_time("ds", Duration::from_secs(2))
+            .unwrap();
+        assert_eq!(net.operating_time("cb").unwrap(), Duration::from_millis(60));
+
+        net.close("cb").unwrap();
+        net.close("ds").unwrap();
+        net.close("es").unwrap();
+        assert_eq!(position("cb"), SwitchPosition::Intermediate);
+        assert_eq!(position("es"), SwitchPosition::Closed);
+        assert_eq!(
+            net.operations(),
+            vec![
+                InFlight::new(
+                    net.component_id("cb").unwrap(),
+                    Operation::Close,
+                    Duration::from_millis(60)
+                ),
+                InFlight::new(
+                    net.component_id("ds").unwrap(),
+                    Operation::Close,
+                    Duration::from_secs(2)
+                ),
+            ]
+        );
+        assert!(matches!(
+            net.open("cb"),
+            Err(Error::InvalidTransition { .. })
+        ));
+
+        net.advance(Duration::from_millis(100)).unwrap();
+        assert_eq!(net.time(), Duration::from_millis(100));
+        assert_eq!(position("cb"), SwitchPosition::Closed);
+        assert_eq!(position("ds"), SwitchPosition::Intermediate);
+        assert_eq!(net.operations().len(), 1);
+
+        net.advance(Duration::from_secs(2)).unwrap();
+        assert_eq!(position("ds"), SwitchPosition::Closed);
+        assert!(net.operations().is_empty());
+
+        let j = net.journal().for_name("cb");
+        let descriptions: Vec<&String> = j.events().iter().map(|e| e.description()).collect();
+        assert_eq!(descriptions[3], "Started to close, completes at 0.060 s");
+        assert_eq!(descriptions[4], "Closed");
+        assert_eq!(j.events()[3].sim_time(), Duration::ZERO);
+        assert_eq!(j.events()[4].sim_time(), Duration::from_millis(60));
+
+        // Undoing an advance turns the clock back and puts the operations it completed back in flight;
+        // undoing the start of an operation cancels it
+        net.undo().unwrap();
+        assert_eq!(net.time(), Duration::from_millis(100));
+        assert_eq!(position("ds"), SwitchPosition::Intermediate);
+        assert_eq!(net.operations().len(), 1);
+        net.advance(Duration::ZERO).unwrap();
+        assert_eq!(position("ds"), SwitchPosition::Intermediate);
+        net.advance(Duration::from_secs(2)).unwrap();
+        assert_eq!(position("ds"), SwitchPosition::Closed);
+        net.undo().unwrap();
+        net.undo().unwrap();
+        assert_eq!(net.time(), Duration::ZERO);
+        net.undo().unwrap();
+        net.undo().unwrap();
+        assert_eq!(position("ds"), SwitchPosition::Open);
+        assert_eq!(position("cb"), SwitchPosition::Intermediate);
+        assert_eq!(net.operations().len(), 1);
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+
+use super::error::Error;
+
+/// Kind of safety [Tag]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TagKind {
+    /// The component must not be operated
+    DoNotOperate,
+    /// The switchgear is locked in the open position; can only be placed on open switchgear
+    LockedOpen,
+    /// The component is out of service
+    OutOfService,
+    /// Work is being done on the component
+    UnderMaintenance,
+}
+
+impl fmt::Display for TagKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kindstr = match self {
+            TagKind::DoNotOperate => "Do Not Operate",
+            TagKind::LockedOpen => "Locked Open",
+            TagKind::OutOfService => "Out of Service",
+            TagKind::UnderMaintenance => "Under Maintenance",
+        };
+        write!(f, "{}", kindstr)
+    }
+}
+
+/// Safety tag placed on a component by an operator; tagged switchgear can not be opened or closed
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tag {
+    name: String,
+    kind: TagKind,
+}
+
+impl Tag {
+    /// Constructor; the name identifies the tag on its component
+    pub fn new(name: &str, kind: TagKind) -> Tag {
+        Tag {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    /// Return the tag name
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Return the kind of tag
+    pub fn kind(&self) -> TagKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.name)
+    }
+}
+
+/// The tags placed on a component
+pub struct Tags {
+    tags: Vec<Tag>,
+}
+
+impl Tags {
+    /// Constructor
+    pub fn new() -> Tags {
+        Tags { tags: vec![] }
+    }
+
+    /// Place a tag; errors if a tag with the same name is already placed
+    pub fn add(&mut self, tag: Tag) -> Result<(), Error> {
+        if self.tags.iter().any(|t| t.name() == tag.name()) {
+            return Err(Error::InvalidArgument(format!(
+                "Tag {} is already placed",
+                tag.name()
+            )));
+        }
+        self.tags.push(tag);
+        Ok(())
+    }
+
+    /// Remove the tag with the given name and return it
+    pub fn remove(&mut self, name: &str) -> Result<Tag, Error> {
+        match self.tags.iter().position(|t| t.name() == name) {
+            Some(i) => Ok(self.tags.remove(i)),
+            None => Err(Error::NotFound {
+                kind: "tag",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Return all tags in the order they were placed
+    pub fn tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+
+    /// True if no tags are placed
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Check that the component with the given name may be operated; errors with the first tag placed
+    pub fn check(&self, component: &str) -> Result<(), Error> {
+        match self.tags.first() {
+            Some(tag) => Err(Error::Tagged {
+                component: component.to_string(),
+                tag: tag.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_add_remove() {
+        let mut tags = Tags::new();
+        assert!(tags.check("cb").is_ok());
+
+        tags.add(Tag::new("T1", TagKind::DoNotOperate)).unwrap();
+        tags.add(Tag::new("T2", TagKind::UnderMaintenance)).unwrap();
+        assert!(tags.add(Tag::new("T1", TagKind::LockedOpen)).is_err());
+        assert_eq!(tags.tags().len(), 2);
+        assert_eq!(
+            tags.check("cb").unwrap_err().to_string(),
+            "Cannot operate cb; tagged Do Not Operate T1"
+        );
+
+        assert_eq!(tags.remove("T1").unwrap().kind(), TagKind::DoNotOperate);
+        assert!(tags.remove("T1").is_err());
+        assert!(tags.check("cb").is_err());
+        tags.remove("T2").unwrap();
+        assert!(tags.is_empty());
+        assert!(tags.check("cb").is_ok());
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+
+use super::error::Error;
+
+/// On-load tap changer of a transformer, on its high voltage winding
+///
+/// Tap positions count from the neutral position 0; each step changes the high voltage winding
+/// by the step size in percent of its rated voltage. By default the range is -8..8 in steps of 1.25 %.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TapChanger {
+    position: i32,
+    min: i32,
+    max: i32,
+    step: f64,
+}
+
+impl TapChanger {
+    /// Names of the parameters, as used by [TapChanger::get] and [TapChanger::set]
+    pub const NAMES: [&'static str; 3] = ["tap_min", "tap_max", "tap_step"];
+
+    /// Constructor, in the neutral position; errors if a parameter is invalid, see [TapChanger::set]
+    pub fn new(min: i32, max: i32, step: f64) -> Result<TapChanger, Error> {
+        // Start without range, so the step can be checked before the lowest position it applies to
+        let mut t = TapChanger {
+            position: 0,
+            min: 0,
+            max: 0,
+            step: 1.0,
+        };
+        for (name, value) in [
+            ("tap_step", step),
+            ("tap_min", min as f64),
+            ("tap_max", max as f64),
+        ] {
+            t.set(name, value)?;
+        }
+        Ok(t)
+    }
+
+    /// Return the tap position
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    /// Return the lowest tap position
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    /// Return the highest tap position
+    pub fn max(&self) -> i32 {
+        self.max
+    }
+
+    /// Return the step size in percent
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Return the factor the tap position applies to the rated voltage of the high voltage winding
+    pub fn factor(&self) -> f64 {
+        1.0 + self.position as f64 * self.step / 100.0
+    }
+
+    /// Return the position after raising; errors if the tap changer is at its highest position
+    pub(crate) fn check_raise(&self) -> Result<i32, Error> {
+        if self.position >= self.max {
+            return Err(Error::TapLimit(self.position));
+        }
+        Ok(self.position + 1)
+    }
+
+    /// Return the position after lowering; errors if the tap changer is at its lowest position
+    pub(crate) fn check_lower(&self) -> Result<i32, Error> {
+        if self.position <= self.min {
+            return Err(Error::TapLimit(self.position));
+        }
+        Ok(self.position - 1)
+    }
+
+    /// Set the tap position; errors if it is out of range
+    pub(crate) fn set_position(&mut self, position: i32) -> Result<(), Error> {
+        if position < self.min || position > self.max {
+            return Err(Error::InvalidArgument(format!(
+                "Tap position {} is outside {}..{}",
+                position, self.min, self.max
+            )));
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    /// Get a parameter by name
+    pub fn get(&self, name: &str) -> Result<f64, Error> {
+        match name {
+            "tap_min" => Ok(self.min as f64),
+            "tap_max" => Ok(self.max as f64),
+            "tap_step" => Ok(self.step),
+            _ => Err(Error::NotFound {
+                kind: "parameter",
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Set a parameter by name
+    ///
+    /// The step size has to be a positive number. The lowest and highest positions have to be whole numbers,
+    /// at or below and at or above both the neutral and the current position.
+    /// The step size and lowest position together have to keep a positive [TapChanger::factor] at the lowest position.
+    pub fn set(&mut self, name: &str, value: f64) -> Result<(), Error> {
+        let invalid = |requirement: &str| {
+            Err(Error::InvalidArgument(format!(
+                "Parameter {} has to be {}",
+                name, requirement
+            )))
+        };
+        if !value.is_finite() {
+            return invalid("a number");
+        }
+        // Factor at the lowest position
+        let lowest = |min: f64, step: f64| 1.0 + min * step / 100.0;
+        match name {
+            "tap_step" if value <= 0.0 => invalid("a positive number"),
+            "tap_step" if lowest(self.min as f64, value) <= 0.0 => {
+                invalid("small enough to keep a positive ratio at the lowest position")
+            }
+            "tap_step" => {
+                self.step = value;
+                Ok(())
+            }
+            "tap_min" | "tap_max" if value.fract() != 0.0 || value.abs() > i32::MAX as f64 => {
+                invalid("a whole number")
+            }
+            "tap_min" if value as i32 > self.position.min(0) => {
+                invalid("at or below the neutral and current position")
+            }
+            "tap_min" if lowest(value, self.step) <= 0.0 => {
+                invalid("high enough to keep a positive ratio at the lowest position")
+            }
+            "tap_min" => {
+                self.min = value as i32;
+                Ok(())
+            }
+            "tap_max" if (value as i32) < self.position.max(0) => {
+                invalid("at or above the neutral and current position")
+            }
+            "tap_max" => {
+                self.max = value as i32;
+                Ok(())
+            }
+            _ => Err(Error::NotFound {
+                kind: "parameter",
+                name: name.to_string(),
+            }),
+        }
+    }
+}
+
+impl Default for TapChanger {
+    fn default() -> Self {
+        TapChanger {
+            position: 0,
+            min: -8,
+            max: 8,
+            step: 1.25,
+        }
+    }
+}
+
+impl fmt::Display for TapChanger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Tap {} ({}..{}, {} % per step)",
+            self.position, self.min, self.max, self.step
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tap_raise_lower() {
+        let mut t = TapChanger::new(-2, 1, 1.25).unwrap();
+        assert_eq!(t.factor(), 1.0);
+        assert_eq!(t.check_raise().unwrap(), 1);
+        t.set_position(1).unwrap();
+        assert_eq!(t.factor(), 1.0125);
+        assert!(matches!(t.check_raise(), Err(Error::TapLimit(1))));
+        assert_eq!(t.check_lower().unwrap(), 0);
+        t.set_position(-2).unwrap();
+        assert_eq!(t.factor(), 0.975);
+        assert!(t.check_lower().is_err());
+        assert_eq!(t.to_string(), "Tap -2 (-2..1, 1.25 % per step)");
+
+        assert!(t.set("tap_min", -1.0).is_err());
+        assert!(t.set("tap_max", -1.0).is_err());
+        assert!(t.set("tap_max", 2.5).is_err());
+        assert!(t.set("tap_step", -1.0).is_err());
+        assert!(t.set("tap_step", 0.0).is_err());
+        assert!(t.set("tap_step", 50.0).is_err());
+        assert!(t.set("tap_min", -80.0).is_err());
+        t.set("tap_step", 49.0).unwrap();
+        assert!(t.factor() > 0.0);
+        t.set("tap_step", 1.25).unwrap();
+        assert!(t.set("tap_neutral", 0.0).is_err());
+        t.set("tap_min", -8.0).unwrap();
+        assert_eq!(t.get("tap_min").unwrap(), -8.0);
+        assert!(t.set_position(-9).is_err());
+        assert!(TapChanger::new(1, 8, 1.0).is_err());
+        assert!(TapChanger::new(-1, 8, 100.0).is_err());
+        assert!(TapChanger::new(-1, 8, 0.0).is_err());
+        assert_eq!(TapChanger::new(-20, 20, 2.5).unwrap().min(), -20);
+        assert_eq!(
+            TapChanger::default().to_string(),
+            "Tap 0 (-8..8, 1.25 % per step)"
+        );
+    }
+}
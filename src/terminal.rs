@@ -1,10 +1,9 @@
-use std::rc::Rc;
+use super::error::Error;
+use super::network::NodeId;
 
-use super::node::Node;
-
-/// Model [Component] terminal, can be connected to a node
+/// Model [Component](super::component::Component) terminal, can be connected to a node
 pub struct Terminal {
-    node: Option<Rc<Node>>,
+    node: Option<NodeId>,
 }
 
 impl Terminal {
@@ -13,10 +12,10 @@ impl Terminal {
         Terminal { node: None }
     }
 
-    /// Connect to node if not already connected
-    pub fn connect(&mut self, node: Rc<Node>) -> Result<(), String> {
+    /// Connect to node if not already connected, see [Network::connect](super::network::Network::connect) to also link the node to the component
+    pub(crate) fn connect(&mut self, node: NodeId) -> Result<(), Error> {
         match self.node {
-            Some(_) => Err("Terminal already connected".to_string()),
+            Some(_) => Err(Error::TerminalConnected),
             None => {
                 self.node = Some(node);
                 Ok(())
@@ -24,40 +23,48 @@ impl Terminal {
         }
     }
 
-    /// Disconnect from node if connected
-    pub fn disconnect(&mut self) -> Result<(), String> {
+    /// Disconnect from node if connected, see [Network::disconnect](super::network::Network::disconnect)
+    pub(crate) fn disconnect(&mut self) -> Result<(), Error> {
         match self.node {
             Some(_) => {
                 self.node = None;
                 Ok(())
             }
-            None => Err("Terminal not connected".to_string()),
+            None => Err(Error::TerminalNotConnected),
         }
     }
 
-    /// Get a reference to the connected node (if connected)
-    pub fn get_node(&self) -> Result<Rc<Node>, String> {
-        match self.node.clone() {
+    /// Get the id of the connected node (if connected)
+    pub fn get_node(&self) -> Result<NodeId, Error> {
+        match self.node {
             Some(node) => Ok(node),
-            None => Err("Terminal not connected".to_string()),
+            None => Err(Error::TerminalNotConnected),
         }
     }
 }
 
+impl Default for Terminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::network::Network;
 
     #[test]
     fn terminal_connect() {
-        let n = Rc::new(Node::new("node"));
+        let net = Network::new("net");
+        let n = net.create_node("node").unwrap();
         let mut t = Terminal::new();
 
         assert!(t.disconnect().is_err());
         assert!(t.get_node().is_err());
 
-        t.connect(n.clone()).unwrap();
-        assert!(Rc::ptr_eq(&t.get_node().unwrap(), &n));
+        t.connect(n).unwrap();
+        assert_eq!(t.get_node().unwrap(), n);
         assert!(t.connect(n).is_err());
 
         t.disconnect().unwrap();
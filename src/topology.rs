@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::analysis::is_closed_switch;
+use super::component::*;
+use super::error::Error;
+use super::network::{Graph, Network, NodeId};
+
+/// An electrical bus; a set of nodes joined by closed switchgear
+pub struct Bus {
+    name: String,
+    nodes: Vec<String>,
+    components: Vec<String>,
+    busbar: Option<(String, f64)>,
+}
+
+impl Bus {
+    /// Return the bus name; the name of its first busbar section if it contains one, otherwise of its first node
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Return the name of the busbar this bus is the main bus of, if it contains a busbar section
+    pub fn busbar(&self) -> Option<&String> {
+        self.busbar.as_ref().map(|(name, _)| name)
+    }
+
+    /// Return the rated voltage in kV of the busbar of this bus, if any
+    pub fn rated_voltage(&self) -> Option<f64> {
+        self.busbar.as_ref().map(|(_, kv)| *kv)
+    }
+
+    /// Return the names of the nodes merged into this bus
+    pub fn nodes(&self) -> &Vec<String> {
+        &self.nodes
+    }
+
+    /// Return the names of the components within this bus; closed switchgear and single terminal components
+    pub fn components(&self) -> &Vec<String> {
+        &self.components
+    }
+}
+
+impl fmt::Display for Bus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bus {} (nodes {})", self.name(), self.nodes().join(", "))
+    }
+}
+
+/// A branch; a multi terminal component between buses
+pub struct Branch {
+    name: String,
+    buses: Vec<Option<usize>>,
+}
+
+impl Branch {
+    /// Return the name of the component this branch represents
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Return the index of the bus connected to each terminal, or None if the terminal is not connected
+    pub fn buses(&self) -> &Vec<Option<usize>> {
+        &self.buses
+    }
+}
+
+/// Bus-branch model of a [Network]
+pub struct Topology {
+    buses: Vec<Bus>,
+    branches: Vec<Branch>,
+    node_bus: HashMap<String, usize>,
+}
+
+impl Topology {
+    /// Reduce the node/component graph of the given network to a bus-branch model.
+    ///
+    /// * Nodes joined by closed circuit breakers or disconnectors are merged into one bus
+    /// * A bus that contains a busbar section is the main bus of that busbar and its voltage level
+    /// * Components with multiple terminals that are not switchgear (transformers) become branches
+    /// * Open switchgear is left out of the model
+    pub fn process(net: &Network) -> Topology {
+        Topology::of(&net.graph())
+    }
+
+    /// Reduce a locked graph, so the model reflects one state of the network, see [Topology::process]
+    fn of(g: &Graph) -> Topology {
+        let ids = g.node_ids();
+        let index: HashMap<NodeId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut parent: Vec<usize> = (0..ids.len()).collect();
+        for c in g.components() {
+            if is_closed_switch(c.as_ref()) {
+                let connected: Vec<usize> = c
+                    .nodes()
+                    .iter()
+                    .filter_map(|n| index.get(n).copied())
+                    .collect();
+                for pair in connected.windows(2) {
+                    let (a, b) = (find(&mut parent, pair[0]), find(&mut parent, pair[1]));
+                    parent[a.max(b)] = a.min(b);
+                }
+            }
+        }
+
+        let mut buses: Vec<Bus> = vec![];
+        let mut root_bus: HashMap<usize, usize> = HashMap::new();
+        let mut node_bus: HashMap<String, usize> = HashMap::new();
+        let mut id_bus: HashMap<NodeId, usize> = HashMap::new();
+        for (i, id) in ids.iter().enumerate() {
+            let n = match g.node(*id) {
+                Ok(n) => n,
+                Err(_) => continue,
+            };
+            let root = find(&mut parent, i);
+            let bus = *root_bus.entry(root).or_insert_with(|| {
+                buses.push(Bus {
+                    name: n.name().clone(),
+                    nodes: vec![],
+                    components: vec![],
+                    busbar: None,
+                });
+                buses.len() - 1
+            });
+            if let (None, Some(busbar)) = (&buses[bus].busbar, g.busbar_of(*id)) {
+                buses[bus].name = n.name().clone();
+                buses[bus].busbar = Some((busbar.name().clone(), busbar.rated_voltage()));
+            }
+            buses[bus].nodes.push(n.name().clone());
+            node_bus.insert(n.name().clone(), bus);
+            id_bus.insert(*id, bus);
+        }
+
+        let mut branches: Vec<Branch> = vec![];
+        for c in g.components() {
+            let mut terminal_buses = vec![];
+            let mut i = 0;
+            while let Ok(t) = c.terminal(i) {
+                terminal_buses.push(match t.read().unwrap().get_node() {
+                    Ok(n) => id_bus.get(&n).copied(),
+                    Err(_) => None,
+                });
+                i += 1;
+            }
+            match c.r#type() {
+                ComponentType::CircuitBreaker | ComponentType::Disconnector => {
+                    if is_closed_switch(c.as_ref()) {
+                        if let Some(Some(bus)) = terminal_buses.iter().find(|x| x.is_some()) {
+                            buses[*bus].components.push(c.name().clone());
+                        }
+                    }
+                }
+                _ if terminal_buses.len() > 1 => branches.push(Branch {
+                    name: c.name().clone(),
+                    buses: terminal_buses,
+                }),
+                _ => {
+                    if let Some(Some(bus)) = terminal_buses.first() {
+                        buses[*bus].components.push(c.name().clone());
+                    }
+                }
+            }
+        }
+
+        Topology {
+            buses,
+            branches,
+            node_bus,
+        }
+    }
+
+    /// Return all buses
+    pub fn buses(&self) -> &Vec<Bus> {
+        &self.buses
+    }
+
+    /// Return all branches
+    pub fn branches(&self) -> &Vec<Branch> {
+        &self.branches
+    }
+
+    /// Return the index of the bus that contains the node with the given name
+    pub fn bus_index(&self, node_name: &str) -> Result<usize, Error> {
+        match self.node_bus.get(node_name) {
+            Some(i) => Ok(*i),
+            None => Err(Error::NotFound {
+                kind: "node",
+                name: node_name.to_string(),
+            }),
+        }
+    }
+
+    /// Return the bus that contains the node with the given name
+    pub fn bus(&self, node_name: &str) -> Result<&Bus, Error> {
+        Ok(&self.buses[self.bus_index(node_name)?])
+    }
+}
+
+impl Network {
+    /// Reduce the network to a bus-branch model, see [Topology::process]
+    pub fn topology(&self) -> Topology {
+        Topology::process(self)
+    }
+}
+
+/// Find the root of the set containing i, compressing the path on the way
+fn find(parent: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut i = i;
+    while parent[i] != root {
+        let next = parent[i];
+        parent[i] = root;
+        i = next;
+    }
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// node1 - cb - node2 - ds - node3 - tf - node4, with a voltage transformer on node4
+    fn create_test_network() -> Network {
+        let net = Network::new("net");
+        for n in ["node1", "node2", "node3", "node4"] {
+            net.create_node(n).unwrap();
+        }
+        net.create_component::<CircuitBreaker>("cb").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.create_component::<Transformer>("tf").unwrap();
+        net.create_component::<VoltageTransformer>("vt").unwrap();
+        net.connect("node1", "cb", 0).unwrap();
+        net.connect("node2", "cb", 1).unwrap();
+        net.connect("node2", "ds", 0).unwrap();
+        net.connect("node3", "ds", 1).unwrap();
+        net.connect("node3", "tf", 0).unwrap();
+        net.connect("node4", "tf", 1).unwrap();
+        net.connect("node4", "vt", 0).unwrap();
+        net
+    }
+
+    #[test]
+    fn topology_open_switchgear() {
+        let net = create_test_network();
+        let t = net.topology();
+
+        assert_eq!(t.buses().len(), 4);
+        assert_ne!(t.bus_index("node1").unwrap(), t.bus_index("node2").unwrap());
+        assert_eq!(
+            t.bus("node4").unwrap().components(),
+            &vec!["vt".to_string()]
+        );
+        assert!(t.bus("does not exist").is_err());
+
+        assert_eq!(t.branches().len(), 1);
+        let tf = &t.branches()[0];
+        assert_eq!(tf.name(), "tf");
+        assert_eq!(
+            tf.buses(),
+            &vec![
+                Some(t.bus_index("node3").unwrap()),
+                Some(t.bus_index("node4").unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn topology_closed_switchgear() {
+        let net = create_test_network();
+        net.get_component("cb").unwrap().close().unwrap();
+        net.get_component("ds").unwrap().close().unwrap();
+        let t = net.topology();
+
+        assert_eq!(t.buses().len(), 2);
+        let bus = t.bus("node3").unwrap();
+        assert_eq!(bus.name(), "node1");
+        assert_eq!(
+            bus.nodes(),
+            &vec![
+                "node1".to_string(),
+                "node2".to_string(),
+                "node3".to_string()
+            ]
+        );
+        assert_eq!(bus.components(), &vec!["cb".to_string(), "ds".to_string()]);
+        assert_eq!(
+            t.branches()[0].buses()[0],
+            Some(t.bus_index("node1").unwrap())
+        );
+
+        net.get_component("cb").unwrap().open().unwrap();
+        let t = net.topology();
+        assert_eq!(t.buses().len(), 3);
+        assert_eq!(t.bus("node3").unwrap().name(), "node2");
+    }
+
+    #[test]
+    fn topology_busbars() {
+        let net = Network::new("net");
+        net.create_node("feeder").unwrap();
+        net.create_busbar("bb1", 110.0, 1).unwrap();
+        net.create_busbar("bb2", 110.0, 1).unwrap();
+        net.add_coupler("coupler", "bb1", "bb2").unwrap();
+        net.create_component::<Disconnector>("ds").unwrap();
+        net.connect("feeder", "ds", 0).unwrap();
+        net.connect("bb2", "ds", 1).unwrap();
+        net.close("ds").unwrap();
+
+        let t = net.topology();
+        assert_eq!(t.buses().len(), 2);
+        let bus = t.bus("feeder").unwrap();
+        assert_eq!(bus.name(), "bb2");
+        assert_eq!(bus.busbar().unwrap(), "bb2");
+        assert_eq!(bus.rated_voltage(), Some(110.0));
+
+        net.close("coupler").unwrap();
+        let t = net.topology();
+        assert_eq!(t.buses().len(), 1);
+        assert_eq!(t.buses()[0].name(), "bb1");
+        assert_eq!(t.buses()[0].busbar().unwrap(), "bb1");
+    }
+}